@@ -1,11 +1,10 @@
-use plotters::prelude::*;
-use textplots::{Chart, Plot, Shape};
-
 use rust_newman_computational_physics::utils::integrate::integrate_simpsons_rule;
+use rust_newman_computational_physics::utils::plot::{output_path, Figure};
+use rust_newman_computational_physics::utils::scalar::Scalar;
 
-// e^(-t^2)
-fn exp_neg_t2(t: f64) -> f64 {
-  (-t*t).exp()
+// e^(-t^2), generic so that it can also be evaluated with dual or complex numbers.
+fn exp_neg_t2<T: Scalar>(t: T) -> T {
+  (-(t*t)).exp()
 }
 
 // We have a scaled variant without the typical leading coefficient:
@@ -15,35 +14,17 @@ fn scaled_erf(x: f64, n: usize) -> f64 {
 }
 
 fn ch05_exercise03_b() -> Result<(), Box<dyn std::error::Error>> {
-  // First a quick plot in the terminal.
   println!("b) A plot of E(x)");
-  Chart::new(75, 30, -4.0, 4.0)
-      .lineplot(&Shape::Continuous(Box::new(|x| scaled_erf(x.into(), 50) as f32)))
-      .display();
-    
-  // Then a plot rendered to a file.
-  let root = BitMapBackend::new("out_ch05_exercise03_b.png", (640, 480)).into_drawing_area();
-  root.fill(&WHITE)?;
-
-  let mut chart = ChartBuilder::on(&root)
-      .caption("E(x)", ("sans-serif", 40).into_font())
-      .margin(10)
-      .x_label_area_size(30)
-      .y_label_area_size(30)
-      .build_cartesian_2d(-4.0..4.0, -1.0..1.0)?;
-
-  chart.configure_mesh().draw()?;
-
-  chart.draw_series(LineSeries::new(
-    (0..=100).map(|x| (x as f64 / 100.0 * 8.0 - 4.0, scaled_erf(x as f64 / 100.0 * 8.0 - 4.0, 50))),
-    &RED,
-  ))?;
-
-  chart.configure_series_labels().draw()?;
-
-  root.present()?;
-
-  println!("   Please see: out_ch05_exercise03_b.png");
+  let figure = Figure::new("E(x)")
+    .x_range(-4.0, 4.0)
+    .y_range(-1.0, 1.0)
+    .x_label("x")
+    .function("", |x| scaled_erf(x, 50));
+  figure.print_terminal()?;
+  let path = output_path("ch05_exercise03_b")?;
+  figure.save(&path)?;
+
+  println!("   Please see: {path}");
   println!();
 
   Ok(())
@@ -65,9 +46,12 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
   ch05_exercise03_b()?;
 
   println!("bonus) A plot of e^(-t^2)");
-  Chart::new(75, 30, -4.0, 4.0)
-      .lineplot(&Shape::Continuous(Box::new(|t| exp_neg_t2(t.into()) as f32)))
-      .display();
+  Figure::new("e^(-t^2)")
+    .x_range(-4.0, 4.0)
+    .y_range(0.0, 1.0)
+    .x_label("t")
+    .function("", exp_neg_t2)
+    .print_terminal()?;
   println!();
 
   Ok(())
@@ -110,27 +94,28 @@ a)
   E(3.0) = 0.8862073466746058
 
 b) A plot of E(x)
-⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⢈⠀⠀⠀⠀⡠⠔⠒⠉⠉⠉⠉⠉⠉⠉⠉⠉⠉⠉⠁ 0.9
-⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠠⠀⠀⡠⠊⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
-⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠐⠀⡜⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
-⡀⢀⠀⡀⢀⠀⡀⢀⠀⡀⢀⠀⡀⢀⠀⡀⢀⠀⣈⢎⠀⡀⢀⠀⡀⢀⠀⡀⢀⠀⡀⢀⠀⡀⢀⠀⡀⢀
-⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⢀⠮⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
-⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⢠⠃⠐⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
-⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⢀⠔⠁⠀⢈⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
-⠤⠤⠤⠤⠤⠤⠤⠤⠤⠤⠤⠤⠒⠊⠁⠀⠀⠀⠠⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀ -0.9
+⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⢈⠀⠀⠀⠀⢀⣀⡤⠤⠤⠤⠤⠤⠤⠤⠤⠤⠤⠤⠤ 1.0
+⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠠⠀⠀⢀⠔⠋⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
+⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠐⠀⡰⠋⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
+⡀⢀⠀⡀⢀⠀⡀⢀⠀⡀⢀⠀⡀⢀⠀⡀⢀⠀⣈⣞⠁⡀⢀⠀⡀⢀⠀⡀⢀⠀⡀⢀⠀⡀⢀⠀⡀⢀
+⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⢠⠾⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
+⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⡴⠃⠐⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
+⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⢀⣀⡴⠊⠀⠀⢈⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
+⠉⠉⠉⠉⠉⠉⠉⠉⠉⠉⠉⠉⠉⠀⠀⠀⠀⠀⠠⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀ -1.0
 -4.0                              4.0
 
-   Please see: out_ch05_exercise03_b.png
+   Please see: out/ch05_exercise03_b.png
 
 bonus) A plot of e^(-t^2)
-⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⡠⢊⠑⡄⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀ 1.0
-⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⡰⠁⠠⠀⠘⡄⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
-⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⢠⠃⠀⠐⠀⠀⠱⡀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
-⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⢀⠇⠀⠀⢈⠀⠀⠀⢣⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
-⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⡜⠀⠀⠀⠠⠀⠀⠀⠈⢆⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
-⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⡜⠀⠀⠀⠀⠐⠀⠀⠀⠀⠈⢆⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
-⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⢀⠜⠀⠀⠀⠀⠀⢈⠀⠀⠀⠀⠀⠈⠢⡀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
-⠤⠤⠤⠤⠤⠤⠤⠤⠤⠔⠒⠁⠀⠀⠀⠀⠀⠀⠠⠀⠀⠀⠀⠀⠀⠀⠈⠒⠢⠤⠤⠤⠤⠤⠤⠤⠤⠄ 0.0
+⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⣰⢊⠑⣆⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀ 1.0
+⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⢠⠃⠠⠀⠘⡄⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
+⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⢀⠇⠀⠐⠀⠀⠸⡀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
+⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⡜⠀⠀⢈⠀⠀⠀⢣⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
+⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⡸⠀⠀⠀⠠⠀⠀⠀⠀⢇⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
+⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⡰⠁⠀⠀⠀⠐⠀⠀⠀⠀⠈⢆⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
+⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⢀⡔⠁⠀⠀⠀⠀⢈⠀⠀⠀⠀⠀⠈⢢⡀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
+⠤⠤⠤⠤⠤⠤⠤⠤⠤⠔⠺⠉⠄⠠⠀⠄⠠⠀⠤⠠⠀⠄⠠⠀⠄⠠⠉⠗⠢⠤⠤⠤⠤⠤⠤⠤⠤⠤ 0.0
 -4.0                              4.0
 
+
 */
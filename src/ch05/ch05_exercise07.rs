@@ -1,6 +1,5 @@
-use plotters::prelude::*;
-use textplots::{Chart, Plot, Shape};
 use rust_newman_computational_physics::utils::integrate::integrate_romberg_adaptive;
+use rust_newman_computational_physics::utils::plot::{output_path, Figure};
 
 // This is a copy of the integrate_trapezoidal_rule_adaptive implementation.
 pub fn _integrate_trapezoidal_rule<F>(a: f64, b: f64, n: usize, h: f64, f: F) -> f64
@@ -98,7 +97,7 @@ pub fn integrate_romberg<F>(a: f64, b: f64, epsilon: f64, f: F) -> f64
     
     print!("    ");
     for k in 1..=rs1.len() {
-      let rkj = rs2[k-1] + (rs2[k-1] - rs1[k-1]) / ((4 as i64).pow(k as u32) - 1) as f64;
+      let rkj = rs2[k-1] + (rs2[k-1] - rs1[k-1]) / (4_i64.pow(k as u32) - 1) as f64;
       print!("{} ", rkj);
       rs2.push(rkj);
     }
@@ -106,7 +105,7 @@ pub fn integrate_romberg<F>(a: f64, b: f64, epsilon: f64, f: F) -> f64
 
     // If the approximation is within our error bounds, we have our answer.
     // See section 5.4, equation (5.49).
-    let error = (rs2[rs2.len() - 2] - rs1[rs2.len() - 2] ) / ((4 as i64).pow(rs1.len() as u32) - 1) as f64;
+    let error = (rs2[rs2.len() - 2] - rs1[rs2.len() - 2] ) / (4_i64.pow(rs1.len() as u32) - 1) as f64;
 
     println!("    Number of slices: {}, Estimated Error: {}", n, error);
 
@@ -133,34 +132,17 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
   let b = 1.0;
 
   println!("bonus) A plot of ( sin(sqrt(100*x)) )^2");
-  Chart::new(75, 30, a as f32, b as f32)
-      .lineplot(&Shape::Continuous(Box::new(|t| integrand(t.into()) as f32)))
-      .display();
+  let figure = Figure::new("( sin(sqrt(100*x)) )^2")
+    .x_range(a, b)
+    .y_range(0.0, 1.0)
+    .x_label("x")
+    .function("", integrand);
+  figure.print_terminal()?;
   println!();
+  let path = output_path("ch05_exercise07_bonus")?;
+  figure.save(&path)?;
 
-  // Then a plot rendered to a file.
-  let root = BitMapBackend::new("out_ch05_exercise07_bonus.png", (640, 480)).into_drawing_area();
-  root.fill(&WHITE)?;
-
-  let mut chart = ChartBuilder::on(&root)
-      .caption("( sin(sqrt(100*x)) )^2", ("sans-serif", 40).into_font())
-      .margin(10)
-      .x_label_area_size(30)
-      .y_label_area_size(30)
-      .build_cartesian_2d(a..b, 0.0..1.0)?;
-
-  chart.configure_mesh().draw()?;
-
-  chart.draw_series(LineSeries::new(
-    (0..=200).map(|x| (x as f64 / 200.0, integrand(x as f64 / 200.0))),
-    &RED,
-  ))?;
-
-  chart.configure_series_labels().draw()?;
-
-  root.present()?;
-
-  println!("   Please see: out_ch05_exercise07_bonus.png");
+  println!("   Please see: {path}");
   println!();
 
 
@@ -181,18 +163,18 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
 /*
 bonus) A plot of ( sin(sqrt(100*x)) )^2
-⣱⡇⠀⠀⠀⠀⠀⡰⠉⢆⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⡠⠊⠉⠢⡀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀ 1.0
-⠜⢱⠀⠀⠀⠀⢰⠁⠀⠘⡄⠀⠀⠀⠀⠀⠀⠀⠀⠀⡰⠁⠀⠀⠀⠘⡄⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
-⠂⢸⠀⠀⠀⠀⡎⠀⠀⠀⢱⠀⠀⠀⠀⠀⠀⠀⠀⢰⠁⠀⠀⠀⠀⠀⠘⡄⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
-⡁⠘⡄⠀⠀⢠⠃⠀⠀⠀⠈⡆⠀⠀⠀⠀⠀⠀⢀⠇⠀⠀⠀⠀⠀⠀⠀⠱⡀⠀⠀⠀⠀⠀⠀⠀⠀⠀
-⠄⠀⡇⠀⠀⡸⠀⠀⠀⠀⠀⢸⠀⠀⠀⠀⠀⠀⡎⠀⠀⠀⠀⠀⠀⠀⠀⠀⢱⠀⠀⠀⠀⠀⠀⠀⠀⠀
-⠂⠀⢣⠀⠀⡇⠀⠀⠀⠀⠀⠀⢇⠀⠀⠀⠀⡸⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⢣⠀⠀⠀⠀⠀⠀⠀⡀
-⡁⠀⠸⡀⢸⠀⠀⠀⠀⠀⠀⠀⠘⡄⠀⠀⡰⠁⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠱⡀⠀⠀⠀⢀⠜⠀
-⠄⠀⠀⠣⠇⠀⠀⠀⠀⠀⠀⠀⠀⠈⠢⠔⠁⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠈⠢⠤⠔⠁⠀⠀ 0.0
+⣡⢇⠀⠀⠀⠀⠀⡰⠉⢆⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⢠⠊⠉⠲⡄⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀ 1.0
+⢼⢸⠀⠀⠀⠀⢀⠇⠀⠈⡆⠀⠀⠀⠀⠀⠀⠀⠀⠀⣰⠁⠀⠀⠀⠘⡄⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
+⡞⢸⠀⠀⠀⠀⡜⠀⠀⠀⢸⠀⠀⠀⠀⠀⠀⠀⠀⢠⠃⠀⠀⠀⠀⠀⠘⡆⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
+⡇⠀⡇⠀⠀⢀⠇⠀⠀⠀⠀⢇⠀⠀⠀⠀⠀⠀⢀⠇⠀⠀⠀⠀⠀⠀⠀⠸⡀⠀⠀⠀⠀⠀⠀⠀⠀⠀
+⡇⠀⢣⠀⠀⢸⠀⠀⠀⠀⠀⠸⡀⠀⠀⠀⠀⠀⡸⠀⠀⠀⠀⠀⠀⠀⠀⠀⠸⡀⠀⠀⠀⠀⠀⠀⠀⠀
+⡇⠀⢸⠀⠀⡎⠀⠀⠀⠀⠀⠀⢣⠀⠀⠀⠀⢰⠁⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠱⡀⠀⠀⠀⠀⠀⠀⡤
+⡇⠀⠘⡄⢰⠁⠀⠀⠀⠀⠀⠀⠈⢆⠀⠀⢠⠃⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠱⡀⠀⠀⠀⢀⡞⠀
+⠇⠠⠀⠷⠮⠀⠄⠠⠀⠄⠠⠀⠄⠨⠣⠴⠣⠀⠄⠠⠀⠄⠠⠀⠄⠠⠀⠄⠠⠀⠄⠨⠢⠤⠴⠃⠄⠠ 0.0
 0.0                               1.0
 
 
-   Please see: out_ch05_exercise07_bonus.png
+   Please see: out/ch05_exercise07_bonus.png
 
 a) Integral of ( sin(sqrt(100*x)) )^2 from 0 to 1 with trapezoidal rule:
 
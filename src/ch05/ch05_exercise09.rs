@@ -1,87 +1,149 @@
-use plotters::prelude::*;
-use textplots::{Chart, Plot, Shape};
-use rust_newman_computational_physics::utils::integrate::integrate_gaussian_quadrature;
-
-const BOLTZMANNS_CONSTANT: f64 = 1.380649e-23; // measured in J/K
+use ndarray::Array1;
+use rust_newman_computational_physics::utils::constants::BOLTZMANN_CONSTANT;
+use rust_newman_computational_physics::utils::differentiate::{
+  complex_step_derivative,
+  differentiate_ridders,
+};
+use rust_newman_computational_physics::utils::integrate::{
+  integrate_gaussian_quadrature_scalar,
+  integrate_parameter_sweep,
+};
+use rust_newman_computational_physics::utils::plot::{output_path, Figure};
+use rust_newman_computational_physics::utils::scalar::Scalar;
+use rust_newman_computational_physics::utils::units::{
+  HeatCapacity,
+  NumberDensity,
+  Temperature,
+  Volume,
+  CUBIC_CENTIMETER,
+  JOULE_PER_KELVIN,
+  KELVIN,
+  PER_CUBIC_METER,
+};
+
+// Generic, as is calculate_heat_capacity, so that they can be evaluated with complex numbers for
+// complex-step differentiation.
+fn debye_integrand<T: Scalar>(x: T) -> T {
+  (x*x*x*x * x.exp())/(x.exp() - 1.0).powi(2)
+}
 
-fn calculate_heat_capacity(temperature: f64, volume: f64, n: usize, number_density: f64, debye_temperature: f64) -> f64 {
-  let integrand = |x: f64| (x*x*x*x * x.exp())/(x.exp() - 1.0).powi(2);
-  let a = 0.0;
+fn calculate_heat_capacity<T: Scalar>(
+  temperature: Temperature<T>,
+  volume: Volume,
+  n: usize,
+  number_density: NumberDensity,
+  debye_temperature: Temperature,
+) -> HeatCapacity<T> {
+  let debye_temperature = debye_temperature.cast();
+  let a = T::from(0.0);
   let b = debye_temperature / temperature;
-  let approx = integrate_gaussian_quadrature(a, b, n, &integrand);
+  let approx = integrate_gaussian_quadrature_scalar(a, b, n, debye_integrand);
 
-  let cv = 9.0 * volume * number_density * BOLTZMANNS_CONSTANT * (temperature / debye_temperature).powi(3) * approx;
+  let atoms = number_density * volume;
+  let boltzmann_constant = HeatCapacity::new(BOLTZMANN_CONSTANT.value, JOULE_PER_KELVIN);
+  (boltzmann_constant * (9.0 * atoms)).cast() * ((temperature / debye_temperature).powi(3) * approx)
+}
 
-  cv
+// The same as calculate_heat_capacity for many temperatures at once, sharing the quadrature
+// sample points between them.
+fn calculate_heat_capacities(
+  temperatures: &[Temperature],
+  volume: Volume,
+  n: usize,
+  number_density: NumberDensity,
+  debye_temperature: Temperature,
+) -> Vec<HeatCapacity> {
+  // The upper limit depends on the temperature, so we substitute x = s * thetaD / T and integrate
+  // over s from 0 to 1 instead. The sweep runs over the bare number of kelvin.
+  let kelvins = temperatures.iter().map(|temperature| temperature.value_in(KELVIN)).collect::<Array1<f64>>();
+  let integrand = |s: f64, kelvin: f64| {
+    let b = debye_temperature / Temperature::new(kelvin, KELVIN);
+    b * debye_integrand(b * s)
+  };
+  let approx = integrate_parameter_sweep(0.0, 1.0, n, &kelvins, integrand);
+
+  let atoms = number_density * volume;
+  let boltzmann_constant = HeatCapacity::new(BOLTZMANN_CONSTANT.value, JOULE_PER_KELVIN);
+  temperatures.iter().zip(approx)
+    .map(|(&temperature, approx)| boltzmann_constant * (9.0 * atoms) * (temperature / debye_temperature).powi(3) * approx)
+    .collect()
 }
 
 fn ch05_exercise09_a() -> Result<(), Box<dyn std::error::Error>> {
-  let number_density = 6.002e28; // Denoted rho, measured in atoms/m^3
-  let volume = 1000.0; // Denoted V, measured in cm^3
-  let volume = volume / (100.0*100.0*100.0); // Now measured in m^3
-  let debye_temperature = 428.0; // Denoted thetaD, measured in K
-  let temperature = 290.0; // Denoted T, measured in K
+  let number_density = NumberDensity::new(6.002e28, PER_CUBIC_METER); // Denoted rho
+  let volume = Volume::new(1000.0, CUBIC_CENTIMETER); // Denoted V
+  let debye_temperature = Temperature::new(428.0, KELVIN); // Denoted thetaD
+  let temperature = Temperature::new(290.0, KELVIN); // Denoted T
   let n: usize = 50; // Denoted N, number of slices
 
   let cv = calculate_heat_capacity(temperature, volume, n, number_density, debye_temperature);
 
-  println!("a) The heat capacity of {volume} cubic meters of aluminum at {temperature}K is approximately:");
-  println!("    {cv} J/K");
+  println!("a) The heat capacity of {volume} of aluminum at {temperature} is approximately:");
+  println!("    {cv}");
   println!();
 
   Ok(())
 }
 
 fn ch05_exercise09_b() -> Result<(), Box<dyn std::error::Error>> {
-  let number_density = 6.002e28; // Denoted rho, measured in atoms/m^3
-  let volume = 1000.0; // Denoted V, measured in cm^3
-  let volume = volume / (100.0*100.0*100.0); // Now measured in m^3
-  let debye_temperature = 428.0; // Denoted thetaD, measured in K
+  let number_density = NumberDensity::new(6.002e28, PER_CUBIC_METER); // Denoted rho
+  let volume = Volume::new(1000.0, CUBIC_CENTIMETER); // Denoted V
+  let debye_temperature = Temperature::new(428.0, KELVIN); // Denoted thetaD
   let n: usize = 50; // Denoted N, number of slices
   let a = 5.0; // Measured in K
   let b = 500.0; // Measured in K
 
-  println!("b) A plot of the specific heat of {volume} cubic meters of aluminum from T={a}K to T={b}K");
-  Chart::new(75, 30, a as f32, b as f32)
-      .lineplot(&Shape::Continuous(Box::new(
-        |t| calculate_heat_capacity(t.into(), volume, n, number_density, debye_temperature) as f32
-      )))
-      .display();
+  println!("b) A plot of the specific heat of {volume} of aluminum from T={a}K to T={b}K");
+  let temperatures = Array1::linspace(a, b, 201).iter().map(|&t| Temperature::new(t, KELVIN)).collect::<Vec<_>>();
+  let heat_capacities = calculate_heat_capacities(&temperatures, volume, n, number_density, debye_temperature);
+
+  let figure = Figure::new("Specific Heat of Aluminum")
+    .x_range(a, b)
+    .y_range(0.0, 2550.0)
+    .x_label("T")
+    .x_unit("K", 1.0)
+    .y_label("C_V")
+    .y_unit("J/K", 1.0)
+    .points("", temperatures.iter().zip(&heat_capacities)
+      .map(|(temperature, cv)| (temperature.value_in(KELVIN), cv.value_in(JOULE_PER_KELVIN)))
+    );
+  figure.print_terminal()?;
   println!();
+  let path = output_path("ch05_exercise09_b")?;
+  figure.save(&path)?;
 
-  // Then a plot rendered to a file.
-  let root = BitMapBackend::new("out_ch05_exercise09_b.png", (640, 480)).into_drawing_area();
-  root.fill(&WHITE)?;
-
-  let mut chart = ChartBuilder::on(&root)
-      .caption("Specific Heat of Aluminum", ("sans-serif", 40).into_font())
-      .margin(10)
-      .x_label_area_size(30)
-      .y_label_area_size(60)
-      .build_cartesian_2d(a..b, 0.0..2550.0)?;
-
-  chart.configure_mesh()
-    .x_label_formatter(&|x| format!("{:} K", *x))
-    .y_label_formatter(&|y| format!("{:} J /K", *y))
-    .draw()?;
-
-  chart.draw_series(LineSeries::new(
-    (0..=200).map(
-      |x| {
-        let frac = x as f64 / 200.0;
-        let t = b * frac + a * (1.0 - frac);
-
-        (t, calculate_heat_capacity(t, volume, n, number_density, debye_temperature))
-      }
-    ),
-    &RED,
-  ))?;
+  println!("   Please see: {path}");
+  println!();
 
-  chart.configure_series_labels().draw()?;
+  Ok(())
+}
 
-  root.present()?;
+fn ch05_exercise09_bonus() -> Result<(), Box<dyn std::error::Error>> {
+  let number_density = NumberDensity::new(6.002e28, PER_CUBIC_METER); // Denoted rho
+  let volume = Volume::new(1000.0, CUBIC_CENTIMETER); // Denoted V
+  let debye_temperature = Temperature::new(428.0, KELVIN); // Denoted thetaD
+  let temperature = Temperature::new(290.0, KELVIN); // Denoted T
+  let n: usize = 50; // Denoted N, number of slices
 
-  println!("   Please see: out_ch05_exercise09_b.png");
+  // Both methods differentiate a function of the bare number of kelvin, which is generic so that the
+  // complex step method can evaluate it at complex temperatures.
+  fn heat_capacity<T: Scalar>(t: T, volume: Volume, n: usize, number_density: NumberDensity, debye_temperature: Temperature) -> T {
+    calculate_heat_capacity(Temperature::new(t, KELVIN), volume, n, number_density, debye_temperature).value_in(JOULE_PER_KELVIN)
+  }
+
+  // Ridders' method starts from a large step, here 10K, and extrapolates towards zero.
+  let dcv_dt = differentiate_ridders(temperature.value_in(KELVIN), 10.0,
+    |t| heat_capacity(t, volume, n, number_density, debye_temperature)
+  );
+
+  // The complex step method should agree to about machine precision.
+  let dcv_dt_complex_step = complex_step_derivative(temperature.value_in(KELVIN),
+    |t| heat_capacity(t, volume, n, number_density, debye_temperature)
+  );
+
+  println!("bonus) The rate of change of the heat capacity with temperature at {temperature} is approximately:");
+  println!("    {} J/K^2 (estimated error {:e} J/K^2)", dcv_dt.value, dcv_dt.error);
+  println!("    {dcv_dt_complex_step} J/K^2 by the complex step method");
   println!();
 
   Ok(())
@@ -90,26 +152,31 @@ fn ch05_exercise09_b() -> Result<(), Box<dyn std::error::Error>> {
 fn main() -> Result<(), Box<dyn std::error::Error>> {
   ch05_exercise09_a()?;
   ch05_exercise09_b()?;
+  ch05_exercise09_bonus()?;
 
   Ok(())
 }
 
 /*
-a) The heat capacity of 0.001 cubic meters of aluminum at 290K is approximately:
-    2234.9797723913557 J/K
-
-b) A plot of the specific heat of 0.001 cubic meters of aluminum from T=5K to T=500K
-⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⢀⣀⣀⠤⠤⠤⠤⠒⠒⠒⠒⠒⠒⠒⠉⠉⠉⠉⠉⠉⠁ 2394.9
-⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⣀⠤⠔⠊⠉⠁⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
-⠀⠀⠀⠀⠀⠀⠀⠀⠀⢀⠔⠊⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
-⠀⠀⠀⠀⠀⠀⠀⢀⠔⠁⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
-⠀⠀⠀⠀⠀⠀⡔⠁⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
-⠀⠀⠀⠀⢀⠎⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
-⠀⠀⠀⡠⠃⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
-⠤⠤⠊⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀ 0.3
+a) The heat capacity of 0.001 m^3 of aluminum at 290 K is approximately:
+    2234.9797723913534 J/K
+
+b) A plot of the specific heat of 0.001 m^3 of aluminum from T=5K to T=500K
+⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⢀⣀⣀⣀⣀⣀⣠⠤⠤⠤⠤⠤⠤⠤⠤⠤ 2550.0
+⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⢀⣀⠤⠴⠒⠒⠋⠉⠉⠉⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
+⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⣠⠴⠊⠉⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
+⠀⠀⠀⠀⠀⠀⠀⢀⡰⠋⠁⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
+⠀⠀⠀⠀⠀⠀⣠⠊⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
+⠀⠀⠀⠀⢀⡴⠁⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
+⠀⠀⠀⣠⠎⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
+⠤⠴⠚⠅⠠⠀⠄⠠⠀⠄⠠⠀⠄⠠⠀⠄⠠⠀⠄⠠⠀⠄⠠⠀⠄⠠⠀⠄⠠⠀⠄⠠⠀⠄⠠⠀⠄⠠ 0.0
 5.0                             500.0
 
 
-   Please see: out_ch05_exercise09_b.png
+   Please see: out/ch05_exercise09_b.png
+
+bonus) The rate of change of the heat capacity with temperature at 290 K is approximately:
+    1.6037352784992736 J/K^2 (estimated error 7.476241847825804e-13 J/K^2)
+    1.603735278498462 J/K^2 by the complex step method
 
 */
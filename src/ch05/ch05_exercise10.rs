@@ -1,15 +1,18 @@
-use plotters::prelude::*;
-use textplots::{Chart, Plot, Shape};
-use rust_newman_computational_physics::utils::integrate::integrate_gaussian_quadrature;
+use rust_newman_computational_physics::utils::differentiate::differentiate_dual;
+use rust_newman_computational_physics::utils::integrate::integrate_gaussian_quadrature_scalar;
+use rust_newman_computational_physics::utils::plot::{output_path, Figure};
+use rust_newman_computational_physics::utils::scalar::Scalar;
+use rust_newman_computational_physics::utils::special::elliptic_k;
 
 const SQRT8: f64 = 2.8284271247461903;
 
-fn calculate_period_of_oscillation(a: f64) -> f64 {
-  let potential = |x: f64| x.powi(4);
+// Generic so that it can also be evaluated with dual numbers to get dT/da.
+fn calculate_period_of_oscillation<T: Scalar>(a: T) -> T {
+  let potential = |x: T| x.powi(4);
   let va = potential(a);
-  let integrand = |x: f64| (va - potential(x)).sqrt().recip();
+  let integrand = |x: T| (va - potential(x)).sqrt().recip();
   let n = 20;
-  let unscaled_approx = integrate_gaussian_quadrature(0.0, a, n, &integrand);
+  let unscaled_approx = integrate_gaussian_quadrature_scalar(T::from(0.0), a, n, integrand);
 
   unscaled_approx * SQRT8
 }
@@ -19,43 +22,38 @@ fn ch05_exercise10_b() -> Result<(), Box<dyn std::error::Error>> {
   let b = 2.0;
 
   println!("b) A plot of the period of the anharmonic oscillation from a={a} to a={b}");
-  Chart::new(75, 30, a as f32, b as f32)
-      .lineplot(&Shape::Continuous(Box::new(
-        |t| calculate_period_of_oscillation(t.into()) as f32
-      )))
-      .display();
-
-  // Then a plot rendered to a file.
-  let root = BitMapBackend::new("out_ch05_exercise10_b.png", (640, 480)).into_drawing_area();
-  root.fill(&WHITE)?;
-
-  let mut chart = ChartBuilder::on(&root)
-      .caption("Period of the Anharmonic Oscillation", ("sans-serif", 40).into_font())
-      .margin(10)
-      .x_label_area_size(30)
-      .y_label_area_size(60)
-      .build_cartesian_2d(a..b, 0.0..350.0)?;
-
-  chart.configure_mesh()
-    .draw()?;
-
-  chart.draw_series(LineSeries::new(
-    (0..=200).map(
-      |x| {
-        let frac = x as f64 / 200.0;
-        let t = b * frac + a * (1.0 - frac);
-
-        (t, calculate_period_of_oscillation(t))
-      }
-    ),
-    &RED,
-  ))?;
-
-  chart.configure_series_labels().draw()?;
-
-  root.present()?;
-
-  println!("   Please see: out_ch05_exercise10_b.png");
+  let figure = Figure::new("Period of the Anharmonic Oscillation")
+    .x_range(a, b)
+    .y_range(0.0, 350.0)
+    .x_label("amplitude")
+    .y_label("period")
+    .samples(201)
+    .function("", calculate_period_of_oscillation);
+  figure.print_terminal()?;
+  let path = output_path("ch05_exercise10_b")?;
+  figure.save(&path)?;
+
+  println!("   Please see: {path}");
+  println!();
+
+  Ok(())
+}
+
+fn ch05_exercise10_bonus() -> Result<(), Box<dyn std::error::Error>> {
+  let a = 1.0;
+  let period = calculate_period_of_oscillation(a);
+  let dperiod_da = differentiate_dual(a, calculate_period_of_oscillation);
+
+  // Substituting x = a s shows that the period is proportional to 1/a for the x^4 potential, so
+  // dT/da = -T/a, which the dual numbers reproduce through the quadrature.
+  println!("bonus) The rate of change of the period with amplitude at a={a} is:");
+  println!("    dT/da = {dperiod_da}");
+  println!("    -T/a  = {}", -period / a);
+  println!();
+
+  // The period integral is a complete elliptic integral, T = 2 K(1/2) / a, which shows how much the
+  // 20 point quadrature loses to the inverse square root singularity at the turning point.
+  println!("    The quadrature gives T = {period}, against the exact 2 K(1/2) / a = {}", 2.0 * elliptic_k(0.5) / a);
   println!();
 
   Ok(())
@@ -63,22 +61,29 @@ fn ch05_exercise10_b() -> Result<(), Box<dyn std::error::Error>> {
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
   ch05_exercise10_b()?;
+  ch05_exercise10_bonus()?;
 
   Ok(())
 }
 
 /*
 b) A plot of the period of the anharmonic oscillation from a=0 to a=2
-⣹⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀ 136.8
+⡁⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀ 350.0
+⠄⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
+⠂⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
+⣡⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
 ⢼⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
-⢺⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
-⡹⡀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
-⠄⡇⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
-⠂⠸⡀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
-⡁⠀⠈⠢⣀⡀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
-⠄⠀⠀⠀⠀⠈⠉⠉⠒⠒⠒⠒⠒⠒⠒⠒⠒⠤⠤⠤⠤⠤⠤⠤⠤⠤⠤⠤⠤⠤⠤⠤⠤⠤⠤⠤⠤⠄ 1.8
+⠚⡄⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
+⡁⢣⣀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
+⠄⠠⠈⠍⠳⠒⠖⠲⠒⠖⠲⠒⠤⠤⠤⠤⠤⠤⠤⠤⠤⠤⠤⠤⠤⠤⠤⠤⠤⠤⠤⠤⠤⠤⠤⠤⠤⠤ 0.0
 0.0                               2.0
 
-   Please see: out_ch05_exercise10_b.png
+   Please see: out/ch05_exercise10_b.png
+
+bonus) The rate of change of the period with amplitude at a=1 is:
+    dT/da = -3.6480940840432203
+    -T/a  = -3.6480940840432203
+
+    The quadrature gives T = 3.6480940840432203, against the exact 2 K(1/2) / a = 3.7081493546027433
 
 */
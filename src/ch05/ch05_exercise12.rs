@@ -1,13 +1,17 @@
 use std::f64::consts::PI;
 
+use rust_newman_computational_physics::utils::constants::{
+  BOLTZMANN_CONSTANT,
+  REDUCED_PLANCK_CONSTANT,
+  SPEED_OF_LIGHT,
+  STEFAN_BOLTZMANN_CONSTANT,
+};
+use rust_newman_computational_physics::utils::estimate::Estimate;
 use rust_newman_computational_physics::utils::integrate::integrate_gaussian_quadrature::{
   integrate_gaussian_quadrature,
   integrate_gaussian_quadrature_adaptive,
 };
-
-const BOLTZMANNS_CONSTANT: f64 = 1.380649e-23; // measured in J/K
-const SPEED_LIGHT: f64 = 299792458.0; // measured in m/s
-const PLANCK_CONSTANT_BAR: f64 = 1.054571817e-34; // J*s
+use rust_newman_computational_physics::utils::measured::Measured;
 
 // We're evaluating the integral of x^3/(e^x-1) from 0 to infinity, but we'll do a substitutions
 // to control the domain of integration so we can use Gaussian quadrature.
@@ -33,14 +37,18 @@ fn evaluate_integral_adaptive(epsilon: f64) -> f64 {
   integrate_gaussian_quadrature_adaptive(0.0, 1.0, epsilon, integrand)
 }
 
-fn approximate_stefan_boltzmann_constant(n: usize) -> f64 {
-  let pi_squared = PI * PI;
-  let c_squared = SPEED_LIGHT * SPEED_LIGHT;
-  let h_bar_cubed = PLANCK_CONSTANT_BAR * PLANCK_CONSTANT_BAR * PLANCK_CONSTANT_BAR;
-  let kb_squared = BOLTZMANNS_CONSTANT * BOLTZMANNS_CONSTANT;
-  let kb_tetrated = kb_squared * kb_squared;
+// The integral with N samples, taking the change on doubling N as its uncertainty.
+fn evaluate_integral_measured(n: usize) -> Measured {
+  let result = evaluate_integral(n);
+  Measured::from(Estimate::new(result, evaluate_integral(2*n) - result))
+}
+
+fn approximate_stefan_boltzmann_constant(n: usize) -> Measured {
+  let c = Measured::from(SPEED_OF_LIGHT);
+  let h_bar = Measured::from(REDUCED_PLANCK_CONSTANT);
+  let kb = Measured::from(BOLTZMANN_CONSTANT);
 
-  kb_tetrated / (4.0 * pi_squared * c_squared * h_bar_cubed) * evaluate_integral(n)
+  kb.powi(4) / (4.0 * PI * PI * c.powi(2) * h_bar.powi(3)) * evaluate_integral_measured(n)
 }
 
 fn ch05_exercise12_b() -> Result<(), Box<dyn std::error::Error>> {
@@ -61,6 +69,7 @@ fn ch05_exercise12_b() -> Result<(), Box<dyn std::error::Error>> {
   println!("    with {n} samples.");
   println!();
   println!("    Our value is off by about {expected_error:e} based on successive approximations.");
+  println!("    Taking that as its uncertainty, the integral is {}.", evaluate_integral_measured(n));
   println!();
 
   let error = (exact - result).abs();
@@ -84,14 +93,14 @@ fn ch05_exercise12_b() -> Result<(), Box<dyn std::error::Error>> {
 fn ch05_exercise12_c() -> Result<(), Box<dyn std::error::Error>> {
   let n = 50;
   let sigma = approximate_stefan_boltzmann_constant(n);
-  let actual = 5.670374419e-8;
-  let error = (sigma - actual).abs();
+  let actual = STEFAN_BOLTZMANN_CONSTANT.value;
+  let error = (sigma.value - actual).abs();
 
   println!("c) Stefan-Boltzmann Constant (calculated with {n} slices):");
   println!();
-  println!("      {sigma:e} J/s/m^2/K^4");
+  println!("      {sigma} J/s/m^2/K^4");
   println!();
-  println!("    The actual value is listed as {actual} on Wikipedia, which is off by {error:e}.");
+  println!("    The exact CODATA 2018 value is {actual:e}, which is off by {error:e}.");
   println!();
 
   Ok(())
@@ -107,26 +116,27 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 /*
 b) The integral of x^3/(e^x-1) from 0 to infinity is:
 
-      6.493939400514871
+      6.493939400514869
 
     To evaluate, we used a substition to make a finite domain of integration
     ranging from 0 to 1. And then we evaluated the integral with Gaussian quadrature
     with 50 samples.
 
-    Our value is off by about 1.7519576900326683e-9 based on successive approximations.
+    Our value is off by about 1.7519612427463471e-9 based on successive approximations.
+    Taking that as its uncertainty, the integral is 6.4939394005 ± 0.0000000018.
 
-    Our value is actually off by 1.7519576900326683e-9 based on the exact value pi^4/15.
+    Our value is actually off by 1.751958578211088e-9 based on the exact value pi^4/15.
 
     Using an adaptive method, we get:
 
-      6.49393940226683
+      6.493939402266829
 
     This new value is off by 8.881784197001252e-16 based on the exact value pi^4/15.
 
 c) Stefan-Boltzmann Constant (calculated with 50 slices):
 
-      5.6703744280776996e-8 J/s/m^2/K^4
+      (5.6703744177 ± 0.0000000015)e-8 J/s/m^2/K^4
 
-    The actual value is listed as 0.00000005670374419 on Wikipedia, which is off by 9.07769974132762e-17.
+    The exact CODATA 2018 value is 5.6703744191844294e-8, which is off by 1.5297739282212785e-17.
 
 */
@@ -0,0 +1,126 @@
+use std::fmt;
+
+// An arbitrarily large unsigned integer, stored as base 2^32 limbs with the least significant
+// first and no trailing zero limbs, so that zero is the empty vector. Only the operations the
+// exact factorials and binomial coefficients need are provided: multiplying and dividing by a
+// machine word.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BigUint {
+  limbs: Vec<u32>,
+}
+
+impl BigUint {
+  pub fn zero() -> BigUint {
+    BigUint { limbs: Vec::new() }
+  }
+
+  pub fn one() -> BigUint {
+    BigUint::from(1_u64)
+  }
+
+  pub fn is_zero(&self) -> bool {
+    self.limbs.is_empty()
+  }
+
+  // self *= m
+  pub fn mul_small(&mut self, m: u32) {
+    if m == 0 {
+      self.limbs.clear();
+      return;
+    }
+    let mut carry = 0_u64;
+    for limb in self.limbs.iter_mut() {
+      let product = *limb as u64 * m as u64 + carry;
+      *limb = product as u32;
+      carry = product >> 32;
+    }
+    if carry > 0 {
+      self.limbs.push(carry as u32);
+    }
+  }
+
+  // self /= d, returning the remainder.
+  pub fn div_rem_small(&mut self, d: u32) -> u32 {
+    assert!(d != 0, "division by zero");
+    let mut remainder = 0_u64;
+    for limb in self.limbs.iter_mut().rev() {
+      let dividend = (remainder << 32) | *limb as u64;
+      *limb = (dividend / d as u64) as u32;
+      remainder = dividend % d as u64;
+    }
+    while self.limbs.last() == Some(&0) {
+      self.limbs.pop();
+    }
+    remainder as u32
+  }
+
+  // The nearest f64, or infinity past f64::MAX. Going through the decimal digits lets the standard
+  // library do the correctly rounded conversion.
+  pub fn to_f64(&self) -> f64 {
+    self.to_string().parse().unwrap()
+  }
+}
+
+impl From<u64> for BigUint {
+  fn from(n: u64) -> BigUint {
+    BigUint::from(n as u128)
+  }
+}
+
+impl From<u128> for BigUint {
+  fn from(mut n: u128) -> BigUint {
+    let mut limbs = Vec::new();
+    while n > 0 {
+      limbs.push(n as u32);
+      n >>= 32;
+    }
+    BigUint { limbs }
+  }
+}
+
+impl fmt::Display for BigUint {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if self.is_zero() {
+      return f.pad_integral(true, "", "0");
+    }
+
+    // Peel off nine decimal digits at a time, least significant first.
+    let chunk = 1_000_000_000;
+    let mut n = self.clone();
+    let mut chunks = Vec::new();
+    while !n.is_zero() {
+      chunks.push(n.div_rem_small(chunk));
+    }
+    let mut digits = chunks.pop().unwrap().to_string();
+    for c in chunks.iter().rev() {
+      digits += &format!("{c:09}");
+    }
+    f.pad_integral(true, "", &digits)
+  }
+}
+
+#[test]
+fn test_big_uint() {
+  assert_eq!(BigUint::zero().to_string(), "0");
+  assert_eq!(BigUint::from(u64::MAX).to_string(), u64::MAX.to_string());
+  assert_eq!(BigUint::from(u128::MAX).to_string(), u128::MAX.to_string());
+  assert_eq!(format!("{:>6}", BigUint::from(1234_u64)), "  1234");
+
+  // 2^200 by repeated doubling, then halved back down.
+  let mut n = BigUint::one();
+  for _ in 0..200 {
+    n.mul_small(2);
+  }
+  assert_eq!(n.to_string(), "1606938044258990275541962092341162602522202993782792835301376");
+  assert_eq!(n.to_f64(), 2.0_f64.powi(200));
+  for _ in 0..200 {
+    assert_eq!(n.div_rem_small(2), 0);
+  }
+  assert_eq!(n, BigUint::one());
+
+  let mut n = BigUint::from(1_000_000_007_u64);
+  assert_eq!(n.div_rem_small(10), 7);
+  assert_eq!(n, BigUint::from(100_000_000_u64));
+  n.mul_small(0);
+  assert!(n.is_zero());
+}
@@ -0,0 +1,312 @@
+use std::fmt;
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::utils::scalar::Scalar;
+
+// A complex number re + i im.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Complex {
+  pub re: f64,
+  pub im: f64,
+}
+
+pub const I: Complex = Complex { re: 0.0, im: 1.0 };
+
+impl Complex {
+  pub const fn new(re: f64, im: f64) -> Complex {
+    Complex { re, im }
+  }
+
+  pub fn from_polar(r: f64, theta: f64) -> Complex {
+    Complex::new(r * theta.cos(), r * theta.sin())
+  }
+
+  pub fn conj(self) -> Complex {
+    Complex::new(self.re, -self.im)
+  }
+
+  // |z|^2
+  pub fn norm_sqr(self) -> f64 {
+    self.re * self.re + self.im * self.im
+  }
+
+  // |z|, computed without overflow for large components.
+  pub fn abs(self) -> f64 {
+    self.re.hypot(self.im)
+  }
+
+  pub fn arg(self) -> f64 {
+    self.im.atan2(self.re)
+  }
+
+  pub fn recip(self) -> Complex {
+    // Smith's algorithm avoids overflow when squaring large components.
+    if self.re.abs() >= self.im.abs() {
+      let r = self.im / self.re;
+      let d = self.re + self.im * r;
+      Complex::new(1.0 / d, -r / d)
+    } else {
+      let r = self.re / self.im;
+      let d = self.re * r + self.im;
+      Complex::new(r / d, -1.0 / d)
+    }
+  }
+
+  pub fn exp(self) -> Complex {
+    Complex::from_polar(self.re.exp(), self.im)
+  }
+}
+
+// The elementary functions on their principal branches. They are written so that a tiny imaginary
+// part survives rounding, which complex-step differentiation relies on; for instance sqrt avoids
+// computing |z| - re when that would cancel.
+impl Scalar for Complex {
+  fn exp(self) -> Complex {
+    Complex::exp(self)
+  }
+
+  fn ln(self) -> Complex {
+    Complex::new(self.abs().ln(), self.arg())
+  }
+
+  fn sqrt(self) -> Complex {
+    if self.re == 0.0 && self.im == 0.0 {
+      return Complex::new(0.0, 0.0);
+    }
+
+    let r = self.abs();
+    if self.re >= 0.0 {
+      let t = ((r + self.re) / 2.0).sqrt();
+      Complex::new(t, self.im / (2.0 * t))
+    } else {
+      let t = ((r - self.re) / 2.0).sqrt();
+      Complex::new(self.im.abs() / (2.0 * t), t.copysign(self.im))
+    }
+  }
+
+  fn powi(self, n: i32) -> Complex {
+    let mut base = if n < 0 { self.recip() } else { self };
+    let mut n = n.unsigned_abs();
+    let mut result = Complex::new(1.0, 0.0);
+    while n > 0 {
+      if n & 1 == 1 {
+        result *= base;
+      }
+      base *= base;
+      n >>= 1;
+    }
+    result
+  }
+
+  fn powf(self, p: f64) -> Complex {
+    if self.re == 0.0 && self.im == 0.0 {
+      return Complex::new(0.0, 0.0);
+    }
+    (Scalar::ln(self) * p).exp()
+  }
+
+  fn recip(self) -> Complex {
+    Complex::recip(self)
+  }
+
+  fn sin(self) -> Complex {
+    Complex::new(self.re.sin() * self.im.cosh(), self.re.cos() * self.im.sinh())
+  }
+
+  fn cos(self) -> Complex {
+    Complex::new(self.re.cos() * self.im.cosh(), -self.re.sin() * self.im.sinh())
+  }
+
+  fn tan(self) -> Complex {
+    Scalar::sin(self) / Scalar::cos(self)
+  }
+
+  fn atan(self) -> Complex {
+    let (a, b) = (self.re, self.im);
+    let re = 0.5 * (2.0 * a).atan2(1.0 - a * a - b * b);
+    let im = 0.25 * (4.0 * b / (a * a + (b - 1.0) * (b - 1.0))).ln_1p();
+    Complex::new(re, im)
+  }
+
+  fn sinh(self) -> Complex {
+    Complex::new(self.re.sinh() * self.im.cos(), self.re.cosh() * self.im.sin())
+  }
+
+  fn cosh(self) -> Complex {
+    Complex::new(self.re.cosh() * self.im.cos(), self.re.sinh() * self.im.sin())
+  }
+
+  fn tanh(self) -> Complex {
+    Scalar::sinh(self) / Scalar::cosh(self)
+  }
+}
+
+impl From<f64> for Complex {
+  fn from(re: f64) -> Complex {
+    Complex::new(re, 0.0)
+  }
+}
+
+impl fmt::Display for Complex {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if self.im.is_sign_negative() {
+      write!(f, "{}-{}i", self.re, -self.im)
+    } else {
+      write!(f, "{}+{}i", self.re, self.im)
+    }
+  }
+}
+
+impl Neg for Complex {
+  type Output = Complex;
+  fn neg(self) -> Complex {
+    Complex::new(-self.re, -self.im)
+  }
+}
+
+impl Add for Complex {
+  type Output = Complex;
+  fn add(self, rhs: Complex) -> Complex {
+    Complex::new(self.re + rhs.re, self.im + rhs.im)
+  }
+}
+
+impl Sub for Complex {
+  type Output = Complex;
+  fn sub(self, rhs: Complex) -> Complex {
+    Complex::new(self.re - rhs.re, self.im - rhs.im)
+  }
+}
+
+impl Mul for Complex {
+  type Output = Complex;
+  fn mul(self, rhs: Complex) -> Complex {
+    Complex::new(self.re * rhs.re - self.im * rhs.im, self.re * rhs.im + self.im * rhs.re)
+  }
+}
+
+impl Div for Complex {
+  type Output = Complex;
+  #[allow(clippy::suspicious_arithmetic_impl)]
+  fn div(self, rhs: Complex) -> Complex {
+    self * rhs.recip()
+  }
+}
+
+impl Add<f64> for Complex {
+  type Output = Complex;
+  fn add(self, rhs: f64) -> Complex {
+    Complex::new(self.re + rhs, self.im)
+  }
+}
+
+impl Sub<f64> for Complex {
+  type Output = Complex;
+  fn sub(self, rhs: f64) -> Complex {
+    Complex::new(self.re - rhs, self.im)
+  }
+}
+
+impl Mul<f64> for Complex {
+  type Output = Complex;
+  fn mul(self, rhs: f64) -> Complex {
+    Complex::new(self.re * rhs, self.im * rhs)
+  }
+}
+
+impl Div<f64> for Complex {
+  type Output = Complex;
+  fn div(self, rhs: f64) -> Complex {
+    Complex::new(self.re / rhs, self.im / rhs)
+  }
+}
+
+impl Add<Complex> for f64 {
+  type Output = Complex;
+  fn add(self, rhs: Complex) -> Complex {
+    rhs + self
+  }
+}
+
+impl Sub<Complex> for f64 {
+  type Output = Complex;
+  fn sub(self, rhs: Complex) -> Complex {
+    Complex::new(self - rhs.re, -rhs.im)
+  }
+}
+
+impl Mul<Complex> for f64 {
+  type Output = Complex;
+  fn mul(self, rhs: Complex) -> Complex {
+    rhs * self
+  }
+}
+
+impl Div<Complex> for f64 {
+  type Output = Complex;
+  fn div(self, rhs: Complex) -> Complex {
+    Complex::from(self) / rhs
+  }
+}
+
+impl AddAssign for Complex {
+  fn add_assign(&mut self, rhs: Complex) {
+    *self = *self + rhs;
+  }
+}
+
+impl SubAssign for Complex {
+  fn sub_assign(&mut self, rhs: Complex) {
+    *self = *self - rhs;
+  }
+}
+
+impl MulAssign for Complex {
+  fn mul_assign(&mut self, rhs: Complex) {
+    *self = *self * rhs;
+  }
+}
+
+#[test]
+fn test_complex_arithmetic() {
+  let a = Complex::new(1.0, 2.0);
+  let b = Complex::new(3.0, -4.0);
+
+  assert_eq!(a + b, Complex::new(4.0, -2.0));
+  assert_eq!(a - b, Complex::new(-2.0, 6.0));
+  assert_eq!(a * b, Complex::new(11.0, 2.0));
+
+  let q = a / b;
+  assert!((q.re - -0.2).abs() < 1.0e-15);
+  assert!((q.im - 0.4).abs() < 1.0e-15);
+  assert_eq!(I * I, Complex::new(-1.0, 0.0));
+
+  // e^(i pi) = -1
+  let z = (I * std::f64::consts::PI).exp();
+  assert!((z - Complex::from(-1.0)).abs() < 1.0e-15);
+}
+
+#[test]
+fn test_complex_elementary_functions() {
+  let z = Complex::new(0.7, -1.3);
+  let close = |a: Complex, b: Complex| (a - b).abs() < 1.0e-14 * b.abs().max(1.0);
+
+  // Inverse pairs and identities.
+  assert!(close(Scalar::ln(z).exp(), z));
+  assert!(close(Scalar::sqrt(z) * Scalar::sqrt(z), z));
+  assert!(close(Scalar::powi(z, 3), z * z * z));
+  assert!(close(Scalar::powi(z, -2), (z * z).recip()));
+  assert!(close(Scalar::powf(z, 2.0), z * z));
+  assert!(close(Scalar::tan(Scalar::atan(z)), z));
+  let (s, c) = (Scalar::sin(z), Scalar::cos(z));
+  assert!(close(s * s + c * c, Complex::from(1.0)));
+  let (sh, ch) = (Scalar::sinh(z), Scalar::cosh(z));
+  assert!(close(ch * ch - sh * sh, Complex::from(1.0)));
+  assert!(close(Scalar::tanh(z), sh / ch));
+
+  // sin(iy) = i sinh(y)
+  assert!(close(Scalar::sin(I * 0.5), I * 0.5_f64.sinh()));
+
+  // The principal square root of a negative number.
+  assert!(close(Scalar::sqrt(Complex::from(-4.0)), I * 2.0));
+}
@@ -0,0 +1,159 @@
+// A selection of the physical constants from the CODATA 2018 adjustment (E. Tiesinga et al., Rev.
+// Mod. Phys. 93, 025010 (2021), https://physics.nist.gov/cuu/Constants), in SI units: the defining
+// constants, the exact constants derived from them, and the commonly used electromagnetic, atomic
+// and nuclear ones, rather than all of the roughly 350 in the table. Others are added here as they
+// are needed, with the value, uncertainty and unit given there.
+//
+// Since the 2019 redefinition of the SI, c, h, e, k, N_A, the caesium frequency and the luminous
+// efficacy are exact, as is anything built from them alone (hbar, the Stefan-Boltzmann constant,
+// the gas constant, ...). Those have zero uncertainty, although the constants which are not
+// terminating decimals are of course rounded to the nearest f64.
+
+// A constant together with its standard uncertainty, in the same unit as the value.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PhysicalConstant {
+  // The name in the CODATA table.
+  pub name: &'static str,
+  pub value: f64,
+  pub uncertainty: f64,
+  pub unit: &'static str,
+}
+
+impl PhysicalConstant {
+  pub const fn exact(name: &'static str, value: f64, unit: &'static str) -> PhysicalConstant {
+    PhysicalConstant { name, value, uncertainty: 0.0, unit }
+  }
+
+  pub const fn measured(name: &'static str, value: f64, uncertainty: f64, unit: &'static str) -> PhysicalConstant {
+    PhysicalConstant { name, value, uncertainty, unit }
+  }
+
+  pub fn is_exact(&self) -> bool {
+    self.uncertainty == 0.0
+  }
+
+  pub fn relative_uncertainty(&self) -> f64 {
+    (self.uncertainty / self.value).abs()
+  }
+}
+
+// The defining constants of the SI.
+pub const HYPERFINE_TRANSITION_FREQUENCY_CS: PhysicalConstant = PhysicalConstant::exact("hyperfine transition frequency of Cs-133", 9_192_631_770.0, "Hz");
+pub const SPEED_OF_LIGHT: PhysicalConstant = PhysicalConstant::exact("speed of light in vacuum", 299_792_458.0, "m s^-1");
+pub const PLANCK_CONSTANT: PhysicalConstant = PhysicalConstant::exact("Planck constant", 6.626_070_15e-34, "J s");
+pub const ELEMENTARY_CHARGE: PhysicalConstant = PhysicalConstant::exact("elementary charge", 1.602_176_634e-19, "C");
+pub const BOLTZMANN_CONSTANT: PhysicalConstant = PhysicalConstant::exact("Boltzmann constant", 1.380_649e-23, "J K^-1");
+pub const AVOGADRO_CONSTANT: PhysicalConstant = PhysicalConstant::exact("Avogadro constant", 6.022_140_76e23, "mol^-1");
+pub const LUMINOUS_EFFICACY: PhysicalConstant = PhysicalConstant::exact("luminous efficacy", 683.0, "lm W^-1");
+
+// Exact constants derived from the defining ones.
+pub const REDUCED_PLANCK_CONSTANT: PhysicalConstant = PhysicalConstant::exact("reduced Planck constant", 1.054_571_817_646_156_5e-34, "J s");
+pub const ELECTRON_VOLT: PhysicalConstant = PhysicalConstant::exact("electron volt", 1.602_176_634e-19, "J");
+pub const MOLAR_GAS_CONSTANT: PhysicalConstant = PhysicalConstant::exact("molar gas constant", 8.314_462_618_153_24, "J mol^-1 K^-1");
+pub const FARADAY_CONSTANT: PhysicalConstant = PhysicalConstant::exact("Faraday constant", 96_485.332_123_310_01, "C mol^-1");
+// sigma = 2 pi^5 k^4 / (15 h^3 c^2)
+pub const STEFAN_BOLTZMANN_CONSTANT: PhysicalConstant = PhysicalConstant::exact("Stefan-Boltzmann constant", 5.670_374_419_184_429_4e-8, "W m^-2 K^-4");
+// b = h c / (k x), where x = 5 + W(-5 e^-5) solves x = 5 (1 - e^-x).
+pub const WIEN_WAVELENGTH_DISPLACEMENT_CONSTANT: PhysicalConstant = PhysicalConstant::exact("Wien wavelength displacement constant", 0.002_897_771_955_185_172_7, "m K");
+pub const JOSEPHSON_CONSTANT: PhysicalConstant = PhysicalConstant::exact("Josephson constant", 483_597_848_416_983.6, "Hz V^-1");
+pub const VON_KLITZING_CONSTANT: PhysicalConstant = PhysicalConstant::exact("von Klitzing constant", 25_812.807_459_304_506, "ohm");
+pub const MAGNETIC_FLUX_QUANTUM: PhysicalConstant = PhysicalConstant::exact("magnetic flux quantum", 2.067_833_848_461_929_5e-15, "Wb");
+pub const CONDUCTANCE_QUANTUM: PhysicalConstant = PhysicalConstant::exact("conductance quantum", 7.748_091_729_863_65e-5, "S");
+
+// Conventional values, exact by definition.
+pub const STANDARD_ACCELERATION_OF_GRAVITY: PhysicalConstant = PhysicalConstant::exact("standard acceleration of gravity", 9.806_65, "m s^-2");
+pub const STANDARD_ATMOSPHERE: PhysicalConstant = PhysicalConstant::exact("standard atmosphere", 101_325.0, "Pa");
+
+// Measured constants.
+pub const NEWTONIAN_CONSTANT_OF_GRAVITATION: PhysicalConstant = PhysicalConstant::measured("Newtonian constant of gravitation", 6.674_30e-11, 1.5e-15, "m^3 kg^-1 s^-2");
+pub const FINE_STRUCTURE_CONSTANT: PhysicalConstant = PhysicalConstant::measured("fine-structure constant", 7.297_352_569_3e-3, 1.1e-12, "");
+pub const INVERSE_FINE_STRUCTURE_CONSTANT: PhysicalConstant = PhysicalConstant::measured("inverse fine-structure constant", 137.035_999_084, 2.1e-8, "");
+pub const VACUUM_ELECTRIC_PERMITTIVITY: PhysicalConstant = PhysicalConstant::measured("vacuum electric permittivity", 8.854_187_812_8e-12, 1.3e-21, "F m^-1");
+pub const VACUUM_MAGNETIC_PERMEABILITY: PhysicalConstant = PhysicalConstant::measured("vacuum magnetic permeability", 1.256_637_062_12e-6, 1.9e-16, "N A^-2");
+pub const CHARACTERISTIC_IMPEDANCE_OF_VACUUM: PhysicalConstant = PhysicalConstant::measured("characteristic impedance of vacuum", 376.730_313_668, 5.7e-8, "ohm");
+pub const ELECTRON_MASS: PhysicalConstant = PhysicalConstant::measured("electron mass", 9.109_383_701_5e-31, 2.8e-40, "kg");
+pub const PROTON_MASS: PhysicalConstant = PhysicalConstant::measured("proton mass", 1.672_621_923_69e-27, 5.1e-37, "kg");
+pub const NEUTRON_MASS: PhysicalConstant = PhysicalConstant::measured("neutron mass", 1.674_927_498_04e-27, 9.5e-37, "kg");
+pub const MUON_MASS: PhysicalConstant = PhysicalConstant::measured("muon mass", 1.883_531_627e-28, 4.2e-36, "kg");
+pub const ATOMIC_MASS_CONSTANT: PhysicalConstant = PhysicalConstant::measured("atomic mass constant", 1.660_539_066_60e-27, 5.0e-37, "kg");
+pub const PROTON_ELECTRON_MASS_RATIO: PhysicalConstant = PhysicalConstant::measured("proton-electron mass ratio", 1_836.152_673_43, 1.1e-7, "");
+pub const ELECTRON_MASS_ENERGY_EQUIVALENT_IN_MEV: PhysicalConstant = PhysicalConstant::measured("electron mass energy equivalent in MeV", 0.510_998_950_00, 1.5e-10, "MeV");
+pub const PROTON_MASS_ENERGY_EQUIVALENT_IN_MEV: PhysicalConstant = PhysicalConstant::measured("proton mass energy equivalent in MeV", 938.272_088_16, 2.9e-7, "MeV");
+pub const ATOMIC_MASS_CONSTANT_ENERGY_EQUIVALENT_IN_MEV: PhysicalConstant = PhysicalConstant::measured("atomic mass constant energy equivalent in MeV", 931.494_102_42, 2.8e-7, "MeV");
+pub const ELECTRON_G_FACTOR: PhysicalConstant = PhysicalConstant::measured("electron g factor", -2.002_319_304_362_56, 3.5e-13, "");
+pub const RYDBERG_CONSTANT: PhysicalConstant = PhysicalConstant::measured("Rydberg constant", 10_973_731.568_160, 2.1e-5, "m^-1");
+pub const RYDBERG_ENERGY: PhysicalConstant = PhysicalConstant::measured("Rydberg energy", 2.179_872_361_103_5e-18, 4.2e-30, "J");
+pub const BOHR_MAGNETON: PhysicalConstant = PhysicalConstant::measured("Bohr magneton", 9.274_010_078_3e-24, 2.8e-33, "J T^-1");
+pub const NUCLEAR_MAGNETON: PhysicalConstant = PhysicalConstant::measured("nuclear magneton", 5.050_783_746_1e-27, 1.5e-36, "J T^-1");
+pub const CLASSICAL_ELECTRON_RADIUS: PhysicalConstant = PhysicalConstant::measured("classical electron radius", 2.817_940_326_2e-15, 1.3e-24, "m");
+pub const COMPTON_WAVELENGTH: PhysicalConstant = PhysicalConstant::measured("Compton wavelength", 2.426_310_238_67e-12, 7.3e-22, "m");
+pub const THOMSON_CROSS_SECTION: PhysicalConstant = PhysicalConstant::measured("Thomson cross section", 6.652_458_732_1e-29, 6.0e-38, "m^2");
+pub const HARTREE_ENERGY_IN_EV: PhysicalConstant = PhysicalConstant::measured("Hartree energy in eV", 27.211_386_245_988, 5.3e-11, "eV");
+
+// Atomic units, in which hbar = m_e = e = 4 pi epsilon_0 = 1. The units of charge, mass and action
+// are the elementary charge, the electron mass and the reduced Planck constant above.
+pub const ATOMIC_UNIT_OF_LENGTH: PhysicalConstant = PhysicalConstant::measured("atomic unit of length", 5.291_772_109_03e-11, 8.0e-21, "m");
+pub const ATOMIC_UNIT_OF_ENERGY: PhysicalConstant = PhysicalConstant::measured("atomic unit of energy", 4.359_744_722_207_1e-18, 8.5e-30, "J");
+pub const ATOMIC_UNIT_OF_TIME: PhysicalConstant = PhysicalConstant::measured("atomic unit of time", 2.418_884_326_585_7e-17, 4.7e-29, "s");
+pub const ATOMIC_UNIT_OF_VELOCITY: PhysicalConstant = PhysicalConstant::measured("atomic unit of velocity", 2.187_691_263_64e6, 3.3e-4, "m s^-1");
+pub const ATOMIC_UNIT_OF_MOMENTUM: PhysicalConstant = PhysicalConstant::measured("atomic unit of momentum", 1.992_851_914_10e-24, 3.0e-34, "kg m s^-1");
+pub const ATOMIC_UNIT_OF_FORCE: PhysicalConstant = PhysicalConstant::measured("atomic unit of force", 8.238_723_498_3e-8, 1.2e-17, "N");
+pub const ATOMIC_UNIT_OF_ELECTRIC_FIELD: PhysicalConstant = PhysicalConstant::measured("atomic unit of electric field", 5.142_206_747_63e11, 7.8e1, "V m^-1");
+pub const BOHR_RADIUS: PhysicalConstant = ATOMIC_UNIT_OF_LENGTH;
+pub const HARTREE_ENERGY: PhysicalConstant = ATOMIC_UNIT_OF_ENERGY;
+
+#[test]
+fn test_constants() {
+  use std::f64::consts::PI;
+
+  let c = SPEED_OF_LIGHT.value;
+  let h = PLANCK_CONSTANT.value;
+  let hbar = REDUCED_PLANCK_CONSTANT.value;
+  let e = ELEMENTARY_CHARGE.value;
+  let k = BOLTZMANN_CONSTANT.value;
+  let n_a = AVOGADRO_CONSTANT.value;
+  let alpha = FINE_STRUCTURE_CONSTANT.value;
+  let m_e = ELECTRON_MASS.value;
+
+  // The exact derived constants, to the few roundings made in evaluating the expressions here.
+  let exact = |constant: PhysicalConstant, derived: f64| {
+    println!("{constant:?} {derived}");
+    assert!(constant.is_exact());
+    assert!((constant.value - derived).abs() < 2.0e-15 * derived.abs());
+  };
+  exact(REDUCED_PLANCK_CONSTANT, h / (2.0 * PI));
+  exact(MOLAR_GAS_CONSTANT, n_a * k);
+  exact(FARADAY_CONSTANT, n_a * e);
+  exact(STEFAN_BOLTZMANN_CONSTANT, 2.0 * PI.powi(5) * k.powi(4) / (15.0 * h.powi(3) * c * c));
+  exact(JOSEPHSON_CONSTANT, 2.0 * e / h);
+  exact(VON_KLITZING_CONSTANT, h / (e * e));
+  exact(MAGNETIC_FLUX_QUANTUM, h / (2.0 * e));
+  exact(CONDUCTANCE_QUANTUM, 2.0 * e * e / h);
+  // The root of x = 5 (1 - e^-x).
+  let x = 4.965_114_231_744_276;
+  exact(WIEN_WAVELENGTH_DISPLACEMENT_CONSTANT, h * c / (k * x));
+
+  // The measured constants are tied together by the exact ones, so each relation has to hold to
+  // within the combined relative uncertainty of the measured constants that enter it.
+  let consistent = |constant: PhysicalConstant, derived: f64, relative_uncertainty: f64| {
+    let relative_error = (constant.value / derived - 1.0).abs();
+    println!("{constant:?} {derived} relative_error={relative_error:e}");
+    assert!(!constant.is_exact());
+    assert!(relative_error < relative_uncertainty.max(4.0e-16));
+  };
+  let u_alpha = FINE_STRUCTURE_CONSTANT.relative_uncertainty();
+  let u_m_e = ELECTRON_MASS.relative_uncertainty();
+  consistent(INVERSE_FINE_STRUCTURE_CONSTANT, 1.0 / alpha, u_alpha);
+  consistent(BOHR_RADIUS, hbar / (m_e * c * alpha), u_alpha + u_m_e);
+  consistent(RYDBERG_CONSTANT, alpha * alpha * m_e * c / (2.0 * h), 2.0 * u_alpha + u_m_e);
+  consistent(HARTREE_ENERGY, alpha * alpha * m_e * c * c, 2.0 * u_alpha + u_m_e);
+  consistent(ATOMIC_UNIT_OF_TIME, hbar / HARTREE_ENERGY.value, 2.0 * u_alpha + u_m_e);
+  consistent(HARTREE_ENERGY_IN_EV, HARTREE_ENERGY.value / e, 2.0 * u_alpha + u_m_e);
+  consistent(VACUUM_ELECTRIC_PERMITTIVITY, e * e / (2.0 * alpha * h * c), u_alpha);
+  consistent(VACUUM_MAGNETIC_PERMEABILITY, 2.0 * alpha * h / (e * e * c), u_alpha);
+  consistent(BOHR_MAGNETON, e * hbar / (2.0 * m_e), u_m_e);
+  consistent(COMPTON_WAVELENGTH, h / (m_e * c), u_m_e);
+  consistent(PROTON_ELECTRON_MASS_RATIO, PROTON_MASS.value / m_e, PROTON_MASS.relative_uncertainty() + u_m_e);
+  assert!(STANDARD_ATMOSPHERE.is_exact());
+  consistent(CHARACTERISTIC_IMPEDANCE_OF_VACUUM, VACUUM_MAGNETIC_PERMEABILITY.value * c, u_alpha);
+}
@@ -0,0 +1,70 @@
+use crate::utils::complex::Complex;
+
+// The derivative of a real analytic function by the complex step method. Since
+//   f(x + ih) = f(x) + ih f'(x) - h^2 f''(x)/2 - ...
+// the derivative is Im f(x + ih)/h with error of order h^2, and unlike a finite difference there
+// is no subtraction, so h can be made tiny and the result is accurate to rounding. The function
+// must be written in terms of Complex, usually by making it generic over Scalar, and must not use
+// non-analytic operations such as abs or comparisons on the real part.
+//
+// See W. Squire and G. Trapp, "Using Complex Variables to Estimate Derivatives of Real Functions",
+// SIAM Review 40 (1998).
+pub fn complex_step_derivative<F>(x: f64, f: F) -> f64
+  where F: Fn(Complex) -> Complex
+{
+  let h = 1.0e-20 * x.abs().max(1.0);
+
+  f(Complex::new(x, h)).im / h
+}
+
+#[test]
+fn test_complex_step_derivative() {
+  use crate::utils::scalar::Scalar;
+
+  // The standard test function of Squire and Trapp, e^x / sqrt(sin^3(x) + cos^3(x)), at 1.5.
+  fn f<T: Scalar>(x: T) -> T {
+    x.exp() / (x.sin().powi(3) + x.cos().powi(3)).sqrt()
+  }
+  let x = 1.5_f64;
+  let derivative = complex_step_derivative(x, f);
+  let exact = {
+    let (s, c) = (x.sin(), x.cos());
+    let g = s.powi(3) + c.powi(3);
+    let dg = 3.0 * s * s * c - 3.0 * c * c * s;
+    x.exp() / g.sqrt() - 0.5 * x.exp() * dg / g.powf(1.5)
+  };
+  println!("derivative={derivative} exact={exact}");
+  assert!((derivative - exact).abs() < 1.0e-15 * exact.abs());
+
+  let derivative = complex_step_derivative(0.3, |x| x.atan() * x.tanh() + x.ln());
+  let exact = 0.3_f64.tanh() / 1.09 + 0.3_f64.atan() / 0.3_f64.cosh().powi(2) + 1.0 / 0.3;
+  assert!((derivative - exact).abs() < 1.0e-15 * exact.abs());
+}
+
+#[test]
+fn test_complex_step_derivative_against_finite_difference() {
+  use crate::utils::differentiate::{differentiate_finite_difference_adaptive, Stencil};
+  use crate::utils::scalar::Scalar;
+
+  // The integrand of ch05_exercise03 and the Debye integrand of ch05_exercise09.
+  fn exp_neg_t2<T: Scalar>(t: T) -> T {
+    (-(t * t)).exp()
+  }
+  fn debye_integrand<T: Scalar>(x: T) -> T {
+    x.powi(4) * x.exp() / (x.exp() - 1.0).powi(2)
+  }
+
+  for x in [0.2, 1.0, 2.5] {
+    let complex_step = complex_step_derivative(x, exp_neg_t2);
+    let finite_difference = differentiate_finite_difference_adaptive(x, 1, 4, Stencil::Central, exp_neg_t2);
+    let exact = -2.0 * x * (-x * x).exp();
+    println!("x={x} complex_step={complex_step} finite_difference={finite_difference:?}");
+    assert!((complex_step - exact).abs() < 1.0e-16);
+    assert!((finite_difference.value - exact).abs() < finite_difference.error * 10.0);
+
+    let complex_step = complex_step_derivative(x, debye_integrand);
+    let finite_difference = differentiate_finite_difference_adaptive(x, 1, 4, Stencil::Central, debye_integrand);
+    println!("x={x} complex_step={complex_step} finite_difference={finite_difference:?}");
+    assert!((complex_step - finite_difference.value).abs() < finite_difference.error * 10.0);
+  }
+}
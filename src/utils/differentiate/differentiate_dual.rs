@@ -0,0 +1,60 @@
+use crate::utils::dual::Dual;
+
+// The derivative of f at x by forward-mode automatic differentiation. The function must be written
+// in terms of Dual, usually by making it generic over Scalar, and the result is exact to rounding.
+pub fn differentiate_dual<F>(x: f64, f: F) -> f64
+  where F: Fn(Dual) -> Dual
+{
+  f(Dual::variable(x)).derivative
+}
+
+#[test]
+fn test_differentiate_dual_harmonic_oscillator() {
+  use std::f64::consts::PI;
+  use crate::utils::scalar::Scalar;
+
+  // The harmonic oscillator wave functions of ch05_exercise13, written generically, satisfy
+  //   psi_n'(x) = sqrt(n/2) psi_{n-1}(x) - sqrt((n+1)/2) psi_{n+1}(x)
+  fn psi<T: Scalar>(n: usize, x: T) -> T {
+    let mut h0 = T::from(1.0);
+    let mut h1 = x * 2.0;
+    for k in 1..n {
+      let h2 = x * h1 * 2.0 - h0 * (2.0 * k as f64);
+      h0 = h1;
+      h1 = h2;
+    }
+    let hermite = if n == 0 { h0 } else { h1 };
+    let norm = (2.0_f64.powi(n as i32) * (1..=n).map(|k| k as f64).product::<f64>() * PI.sqrt()).sqrt();
+    hermite * (-(x * x) * 0.5).exp() / norm
+  }
+
+  for n in 1..6 {
+    for x in [-2.0, -0.3, 0.0, 1.1, 3.0] {
+      let derivative = differentiate_dual(x, |x| psi(n, x));
+      let exact = (n as f64 / 2.0).sqrt() * psi(n - 1, x) - ((n + 1) as f64 / 2.0).sqrt() * psi(n + 1, x);
+      assert!((derivative - exact).abs() < 1.0e-14, "n={n} x={x}: {derivative} != {exact}");
+    }
+  }
+}
+
+#[test]
+fn test_differentiate_dual_integral() {
+  use crate::utils::integrate::integrate_gaussian_quadrature_scalar;
+  use crate::utils::scalar::Scalar;
+
+  // d/dp of the integral from 0 to 1 of e^(px) dx is ((p-1)e^p + 1)/p^2.
+  let p = 1.5_f64;
+  let derivative = differentiate_dual(p,
+    |p| integrate_gaussian_quadrature_scalar(Dual::constant(0.0), Dual::constant(1.0), 20, |x| (p * x).exp())
+  );
+  let exact = ((p - 1.0) * p.exp() + 1.0) / (p * p);
+  println!("derivative={derivative} exact={exact}");
+  assert!((derivative - exact).abs() < 1.0e-14);
+
+  // And through the upper limit, d/db of the integral from 0 to b of cos(x) dx is cos(b).
+  let b = 0.9_f64;
+  let derivative = differentiate_dual(b,
+    |b| integrate_gaussian_quadrature_scalar(Dual::constant(0.0), b, 20, |x| x.cos())
+  );
+  assert!((derivative - b.cos()).abs() < 1.0e-14);
+}
@@ -0,0 +1,193 @@
+use crate::utils::Estimate;
+
+// Which side of x the sample points of a finite difference lie on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stencil {
+  Forward,
+  Backward,
+  Central,
+}
+
+// Weights w_i such that the sum of w_i f(points[i]) approximates the m-th derivative of f at x0,
+// using the recursion of B. Fornberg, "Generation of Finite Difference Formulas on Arbitrarily
+// Spaced Grids", Mathematics of Computation 51 (1988). The points need not be equally spaced, but
+// there must be more of them than m.
+pub fn finite_difference_weights(x0: f64, points: &[f64], m: usize) -> Vec<f64> {
+  let n = points.len();
+  assert!(n > m, "{n} points cannot approximate a derivative of order {m}");
+
+  // c[i][k] is the weight of points[i] for the k-th derivative.
+  let mut c = vec![vec![0.0; m + 1]; n];
+  c[0][0] = 1.0;
+
+  let mut c1 = 1.0;
+  let mut c4 = points[0] - x0;
+  for i in 1..n {
+    let mn = i.min(m);
+    let mut c2 = 1.0;
+    let c5 = c4;
+    c4 = points[i] - x0;
+
+    for j in 0..i {
+      let c3 = points[i] - points[j];
+      c2 *= c3;
+
+      if j == i - 1 {
+        for k in (1..=mn).rev() {
+          c[i][k] = c1 * ((k as f64) * c[i-1][k-1] - c5 * c[i-1][k]) / c2;
+        }
+        c[i][0] = -c1 * c5 * c[i-1][0] / c2;
+      }
+
+      for k in (1..=mn).rev() {
+        c[j][k] = (c4 * c[j][k] - (k as f64) * c[j][k-1]) / c3;
+      }
+      c[j][0] = c4 * c[j][0] / c3;
+    }
+
+    c1 = c2;
+  }
+
+  c.iter().map(|row| row[m]).collect()
+}
+
+// The offsets, in units of the step size, of the fewest equally spaced points giving the m-th
+// derivative with truncation error of order h^accuracy. Central differences have only even orders
+// of accuracy.
+pub fn stencil_offsets(stencil: Stencil, m: usize, accuracy: usize) -> Vec<f64> {
+  assert!(m >= 1, "the derivative order must be at least one");
+  assert!(accuracy >= 1, "the order of accuracy must be at least one");
+
+  match stencil {
+    Stencil::Forward => {
+      (0..m + accuracy).map(|i| i as f64).collect()
+    },
+    Stencil::Backward => {
+      (0..m + accuracy).rev().map(|i| -(i as f64)).collect()
+    },
+    Stencil::Central => {
+      assert!(accuracy.is_multiple_of(2), "central differences have even orders of accuracy, not {accuracy}");
+      let points = 2 * m.div_ceil(2) - 1 + accuracy;
+      let r = (points / 2) as i64;
+      (-r..=r).map(|i| i as f64).collect()
+    },
+  }
+}
+
+// The m-th derivative of f at x from equally spaced samples a distance h apart.
+pub fn differentiate_finite_difference<F>(x: f64, h: f64, m: usize, accuracy: usize, stencil: Stencil, f: F) -> f64
+  where F: Fn(f64) -> f64
+{
+  let offsets = stencil_offsets(stencil, m, accuracy);
+  let weights = finite_difference_weights(0.0, &offsets, m);
+
+  let total: f64 = offsets.iter().zip(weights.iter()).map(|(&k, &w)| w * f(x + k * h)).sum();
+
+  total / h.powi(m as i32)
+}
+
+// As differentiate_finite_difference, but the step size is chosen to balance the truncation error,
+// which grows like h^accuracy, against the round-off error, which grows like epsilon/h^m (see
+// section 5.10.2). The truncation error is estimated by comparing with the result for twice the
+// step size and the round-off error from the size of the terms being cancelled.
+pub fn differentiate_finite_difference_adaptive<F>(x: f64, m: usize, accuracy: usize, stencil: Stencil, f: F) -> Estimate
+  where F: Fn(f64) -> f64
+{
+  let offsets = stencil_offsets(stencil, m, accuracy);
+  let weights = finite_difference_weights(0.0, &offsets, m);
+
+  // Balancing C h^p against epsilon/h^m gives h proportional to epsilon^(1/(p+m)), scaled to the
+  // size of x. Rounding x+h and subtracting again makes h exactly representable.
+  let scale = x.abs().max(1.0);
+  let h = f64::EPSILON.powf(1.0 / ((accuracy + m) as f64)) * scale;
+  let h = (x + h) - x;
+
+  let difference = |h: f64| -> (f64, f64) {
+    let terms: Vec<f64> = offsets.iter().zip(weights.iter()).map(|(&k, &w)| w * f(x + k * h)).collect();
+    let total: f64 = terms.iter().sum();
+    let magnitude: f64 = terms.iter().map(|t| t.abs()).sum();
+    let hm = h.powi(m as i32);
+    (total / hm, magnitude / hm)
+  };
+
+  let (value, magnitude) = difference(h);
+  let (value_2h, _) = difference(2.0 * h);
+
+  let truncation_error = (value - value_2h).abs() / (2.0_f64.powi(accuracy as i32) - 1.0);
+  let round_off_error = f64::EPSILON * magnitude;
+
+  Estimate::new(value, truncation_error + round_off_error)
+}
+
+#[test]
+fn test_finite_difference_weights() {
+  let check = |actual: Vec<f64>, expected: &[f64]| {
+    println!("{actual:?}");
+    assert_eq!(actual.len(), expected.len());
+    for (a, e) in actual.iter().zip(expected) {
+      assert!((a - e).abs() < 1.0e-14);
+    }
+  };
+
+  check(finite_difference_weights(0.0, &stencil_offsets(Stencil::Forward, 1, 1), 1), &[-1.0, 1.0]);
+  check(finite_difference_weights(0.0, &stencil_offsets(Stencil::Backward, 1, 1), 1), &[-1.0, 1.0]);
+  check(finite_difference_weights(0.0, &stencil_offsets(Stencil::Central, 1, 2), 1), &[-0.5, 0.0, 0.5]);
+  check(finite_difference_weights(0.0, &stencil_offsets(Stencil::Central, 2, 2), 2), &[1.0, -2.0, 1.0]);
+  check(
+    finite_difference_weights(0.0, &stencil_offsets(Stencil::Central, 1, 4), 1),
+    &[1.0/12.0, -2.0/3.0, 0.0, 2.0/3.0, -1.0/12.0],
+  );
+  check(
+    finite_difference_weights(0.0, &stencil_offsets(Stencil::Forward, 2, 2), 2),
+    &[2.0, -5.0, 4.0, -1.0],
+  );
+  check(
+    finite_difference_weights(0.0, &stencil_offsets(Stencil::Central, 3, 2), 3),
+    &[-0.5, 1.0, 0.0, -1.0, 0.5],
+  );
+
+  // Unequally spaced points reproduce the derivative of a quadratic exactly.
+  let points = [0.0, 0.3, 1.0];
+  let weights = finite_difference_weights(0.1, &points, 1);
+  let approx: f64 = points.iter().zip(weights.iter()).map(|(x, w)| w * x * x).sum();
+  assert!((approx - 0.2).abs() < 1.0e-14);
+}
+
+#[test]
+fn test_differentiate_finite_difference() {
+  // The error of central differences shrinks by 2^accuracy when the step size halves.
+  let x = 1.0_f64;
+  let exact = x.cos();
+  for accuracy in [2, 4, 6] {
+    let error1 = (differentiate_finite_difference(x, 0.1, 1, accuracy, Stencil::Central, f64::sin) - exact).abs();
+    let error2 = (differentiate_finite_difference(x, 0.05, 1, accuracy, Stencil::Central, f64::sin) - exact).abs();
+    let ratio = error1 / error2;
+    println!("accuracy={accuracy} error1={error1} error2={error2} ratio={ratio}");
+    assert!((ratio / 2.0_f64.powi(accuracy as i32) - 1.0).abs() < 0.05);
+  }
+}
+
+#[test]
+fn test_differentiate_finite_difference_adaptive() {
+  // The derivatives of e^x are all e^x.
+  let x = 0.7_f64;
+  let exact = x.exp();
+
+  for stencil in [Stencil::Forward, Stencil::Backward, Stencil::Central] {
+    for m in 1..=4 {
+      for accuracy in [2, 4] {
+        let estimate = differentiate_finite_difference_adaptive(x, m, accuracy, stencil, f64::exp);
+        let error = (estimate.value - exact).abs();
+        println!("{stencil:?} m={m} accuracy={accuracy} estimate={estimate:?} error={error}");
+        assert!(error < 10.0 * estimate.error);
+        if m == 1 {
+          assert!(error < 1.0e-7);
+        }
+      }
+    }
+  }
+
+  let estimate = differentiate_finite_difference_adaptive(1.0e6, 1, 6, Stencil::Central, |x| x.ln());
+  println!("estimate={estimate:?}");
+  assert!((estimate.value - 1.0e-6).abs() < 1.0e-6 * 1.0e-8);
+}
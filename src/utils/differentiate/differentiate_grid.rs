@@ -0,0 +1,188 @@
+use ndarray::{Array, Array2, Array3, Axis, Dimension};
+
+use crate::utils::differentiate::finite_difference_weights;
+
+// Derivatives of data sampled on a regular grid, such as an Array2 indexed [i][j] with the first
+// index along the first axis. Axis k is treated as the coordinate x_k, with spacing[k] between
+// neighbouring samples, so for an image stored by rows the derivative along axis 0 is d/dy.
+//
+// Interior points use central differences. Near the edges, where a central stencil would run off
+// the grid, the same number of points is shifted inwards to give a one-sided difference of the
+// same order of accuracy, so every output has the shape of its input.
+
+// The first sample of the stencil for point i of n, and its weights.
+fn grid_stencil(i: usize, n: usize, m: usize, accuracy: usize) -> (usize, Vec<f64>) {
+  let central_width = 2 * m.div_ceil(2) - 1 + accuracy;
+  let half_width = central_width / 2;
+
+  let (start, width) = if i >= half_width && i + half_width < n {
+    (i - half_width, central_width)
+  } else {
+    let width = m + accuracy;
+    (i.saturating_sub(width / 2).min(n - width), width)
+  };
+
+  let offsets: Vec<f64> = (0..width).map(|k| (start + k) as f64 - i as f64).collect();
+  (start, finite_difference_weights(0.0, &offsets, m))
+}
+
+// The m-th derivative along one axis, with truncation error of order spacing^accuracy.
+pub fn differentiate_grid<D>(field: &Array<f64, D>, axis: usize, spacing: f64, m: usize, accuracy: usize) -> Array<f64, D>
+  where D: Dimension
+{
+  assert!(accuracy.is_multiple_of(2), "grid derivatives have even orders of accuracy, not {accuracy}");
+  let n = field.len_of(Axis(axis));
+  assert!(n >= m + accuracy, "axis {axis} has {n} points, too few for this stencil");
+
+  let stencils: Vec<(usize, Vec<f64>)> = (0..n).map(|i| grid_stencil(i, n, m, accuracy)).collect();
+  let scale = spacing.powi(m as i32);
+
+  let mut result = Array::zeros(field.raw_dim());
+  for (lane, mut lane_result) in field.lanes(Axis(axis)).into_iter().zip(result.lanes_mut(Axis(axis))) {
+    for (i, (start, weights)) in stencils.iter().enumerate() {
+      let total: f64 = weights.iter().enumerate().map(|(k, w)| w * lane[start + k]).sum();
+      lane_result[i] = total / scale;
+    }
+  }
+
+  result
+}
+
+// The partial derivatives along every axis.
+pub fn gradient<D>(field: &Array<f64, D>, spacing: &[f64], accuracy: usize) -> Vec<Array<f64, D>>
+  where D: Dimension
+{
+  assert_eq!(spacing.len(), field.ndim(), "one spacing per axis");
+
+  (0..field.ndim()).map(|axis| differentiate_grid(field, axis, spacing[axis], 1, accuracy)).collect()
+}
+
+// The sum of the second partial derivatives along every axis.
+pub fn laplacian<D>(field: &Array<f64, D>, spacing: &[f64], accuracy: usize) -> Array<f64, D>
+  where D: Dimension
+{
+  assert_eq!(spacing.len(), field.ndim(), "one spacing per axis");
+
+  let mut result = Array::zeros(field.raw_dim());
+  for (axis, &h) in spacing.iter().enumerate() {
+    result += &differentiate_grid(field, axis, h, 2, accuracy);
+  }
+
+  result
+}
+
+// The divergence of a vector field given as one array per component, components[k] being the
+// component along axis k.
+pub fn divergence<D>(components: &[Array<f64, D>], spacing: &[f64], accuracy: usize) -> Array<f64, D>
+  where D: Dimension
+{
+  assert_eq!(components.len(), spacing.len(), "one component per axis");
+
+  let mut result = Array::zeros(components[0].raw_dim());
+  for (axis, (component, &h)) in components.iter().zip(spacing).enumerate() {
+    assert_eq!(component.ndim(), spacing.len(), "one spacing per axis");
+    result += &differentiate_grid(component, axis, h, 1, accuracy);
+  }
+
+  result
+}
+
+// The curl of a planar vector field (fx, fy), which is the scalar dfy/dx - dfx/dy.
+pub fn curl_2d(fx: &Array2<f64>, fy: &Array2<f64>, spacing: [f64; 2], accuracy: usize) -> Array2<f64> {
+  differentiate_grid(fy, 0, spacing[0], 1, accuracy) - differentiate_grid(fx, 1, spacing[1], 1, accuracy)
+}
+
+// The curl of a vector field (fx, fy, fz) in three dimensions.
+pub fn curl_3d(fx: &Array3<f64>, fy: &Array3<f64>, fz: &Array3<f64>, spacing: [f64; 3], accuracy: usize) -> [Array3<f64>; 3] {
+  let d = |f: &Array3<f64>, axis: usize| differentiate_grid(f, axis, spacing[axis], 1, accuracy);
+
+  [
+    d(fz, 1) - d(fy, 2),
+    d(fx, 2) - d(fz, 0),
+    d(fy, 0) - d(fx, 1),
+  ]
+}
+
+#[test]
+fn test_differentiate_grid_polynomials() {
+  use ndarray::Array1;
+
+  // Stencils of accuracy p are exact for polynomials of degree p, edges included.
+  let x: Array1<f64> = Array1::linspace(-1.0, 2.0, 16);
+  let h = x[1] - x[0];
+  for accuracy in [2, 4, 6] {
+    let p = accuracy as i32;
+    let f = x.mapv(|x| x.powi(p));
+    let exact = x.mapv(|x| (p as f64) * x.powi(p - 1));
+    let approx = differentiate_grid(&f, 0, h, 1, accuracy);
+    let error = (&approx - &exact).mapv(f64::abs).fold(0.0_f64, |a, &b| a.max(b));
+    println!("accuracy={accuracy} first derivative error={error}");
+    assert!(error < 1.0e-10);
+
+    let exact = x.mapv(|x| (p * (p - 1)) as f64 * x.powi(p - 2));
+    let approx = differentiate_grid(&f, 0, h, 2, accuracy);
+    let error = (&approx - &exact).mapv(f64::abs).fold(0.0_f64, |a, &b| a.max(b));
+    println!("accuracy={accuracy} second derivative error={error}");
+    assert!(error < 1.0e-8);
+  }
+}
+
+#[test]
+fn test_gradient_and_laplacian() {
+  let (nx, ny) = (41, 61);
+  let (hx, hy) = (0.05, 0.03);
+  let field = Array2::from_shape_fn((nx, ny), |(i, j)| {
+    let (x, y) = (i as f64 * hx, j as f64 * hy);
+    x.sin() * (2.0 * y).cos()
+  });
+
+  let max_error = |approx: &Array2<f64>, exact: &dyn Fn(f64, f64) -> f64| {
+    approx.indexed_iter()
+      .map(|((i, j), a)| (a - exact(i as f64 * hx, j as f64 * hy)).abs())
+      .fold(0.0_f64, f64::max)
+  };
+
+  let errors: Vec<f64> = [2, 4].iter().map(|&accuracy| {
+    let g = gradient(&field, &[hx, hy], accuracy);
+    assert_eq!(g[0].dim(), field.dim());
+    let ex = max_error(&g[0], &|x, y| x.cos() * (2.0 * y).cos());
+    let ey = max_error(&g[1], &|x, y| -2.0 * x.sin() * (2.0 * y).sin());
+    let el = max_error(&laplacian(&field, &[hx, hy], accuracy), &|x, y| -5.0 * x.sin() * (2.0 * y).cos());
+    println!("accuracy={accuracy} errors {ex} {ey} {el}");
+    ex.max(ey).max(el)
+  }).collect();
+
+  assert!(errors[0] < 5.0e-2);
+  assert!(errors[1] < 1.0e-4);
+}
+
+#[test]
+fn test_divergence_and_curl() {
+  let n = 12;
+  let h = 0.1;
+  let coordinate = |axis: usize| Array3::from_shape_fn((n, n, n), |index| {
+    let index = [index.0, index.1, index.2];
+    index[axis] as f64 * h
+  });
+  let (x, y, z) = (coordinate(0), coordinate(1), coordinate(2));
+
+  // div (x, y, z) = 3
+  let div = divergence(&[x.clone(), y.clone(), z.clone()], &[h, h, h], 2);
+  assert!(div.iter().all(|d| (d - 3.0).abs() < 1.0e-12));
+
+  // curl (-y, x, 0) = (0, 0, 2), and the curl of a gradient vanishes.
+  let zero = Array3::zeros((n, n, n));
+  let [cx, cy, cz] = curl_3d(&(-&y), &x, &zero, [h, h, h], 2);
+  assert!(cx.iter().chain(cy.iter()).all(|c| c.abs() < 1.0e-12));
+  assert!(cz.iter().all(|c| (c - 2.0).abs() < 1.0e-12));
+
+  let phi = &x * &y * &z;
+  let g = gradient(&phi, &[h, h, h], 2);
+  let [cx, cy, cz] = curl_3d(&g[0], &g[1], &g[2], [h, h, h], 2);
+  assert!(cx.iter().chain(cy.iter()).chain(cz.iter()).all(|c| c.abs() < 1.0e-10));
+
+  let x2 = Array2::from_shape_fn((n, n), |(i, _)| i as f64 * h);
+  let y2 = Array2::from_shape_fn((n, n), |(_, j)| j as f64 * h);
+  let c = curl_2d(&(-&y2), &x2, [h, h], 4);
+  assert!(c.iter().all(|c| (c - 2.0).abs() < 1.0e-12));
+}
@@ -0,0 +1,91 @@
+use crate::utils::Estimate;
+
+// Ridders' method for the first derivative of f at x. Starting from the central difference with a
+// deliberately large step h, the step is repeatedly shrunk and a Neville tableau extrapolates the
+// central differences to zero step size, much as Romberg integration extrapolates the
+// trapezoidal rule. The error is estimated from neighbouring entries of the tableau, and we stop
+// once the error of the newest row grows, which is where round-off starts to dominate.
+//
+// See C. J. F. Ridders, "Accurate computation of F'(x) and F'(x)F''(x)", Advances in Engineering
+// Software 4 (1982), and the dfridr routine of Numerical Recipes.
+pub fn differentiate_ridders<F>(x: f64, h: f64, f: F) -> Estimate
+  where F: Fn(f64) -> f64
+{
+  assert!(h != 0.0, "the initial step size must be nonzero");
+
+  // Each step is 1/1.4 of the last, and the tableau has at most this many rows.
+  let shrink = 1.4;
+  let shrink_squared = shrink * shrink;
+  let rows = 10;
+  let safe = 2.0;
+
+  let central_difference = |h: f64| (f(x + h) - f(x - h)) / (2.0 * h);
+
+  let mut h = h;
+  let mut previous = vec![central_difference(h)];
+  let mut answer = previous[0];
+  let mut error = f64::INFINITY;
+
+  for _ in 1..rows {
+    h /= shrink;
+
+    let mut current = vec![central_difference(h)];
+    let mut factor = shrink_squared;
+    for j in 1..=previous.len() {
+      let extrapolated = (current[j-1] * factor - previous[j-1]) / (factor - 1.0);
+      factor *= shrink_squared;
+
+      let tableau_error = (extrapolated - current[j-1]).abs().max((extrapolated - previous[j-1]).abs());
+      if tableau_error <= error {
+        error = tableau_error;
+        answer = extrapolated;
+      }
+
+      current.push(extrapolated);
+    }
+
+    // Higher order extrapolations are getting worse, so round-off has taken over.
+    let last = current.len() - 1;
+    if (current[last] - previous[last - 1]).abs() >= safe * error {
+      break;
+    }
+
+    previous = current;
+  }
+
+  Estimate::new(answer, error)
+}
+
+#[test]
+fn test_differentiate_ridders() {
+  let x = 1.0_f64;
+
+  let estimate = differentiate_ridders(x, 0.5, f64::sin);
+  let error = (estimate.value - x.cos()).abs();
+  println!("estimate={estimate:?} error={error}");
+  assert!(error < 1.0e-13);
+  assert!(error <= estimate.error * 10.0);
+
+  let estimate = differentiate_ridders(x, 0.1, |x| x.exp() / (x.sin() - x*x));
+  let exact = {
+    let d = x.sin() - x*x;
+    x.exp() / d - x.exp() * (x.cos() - 2.0*x) / (d*d)
+  };
+  let error = (estimate.value - exact).abs();
+  println!("estimate={estimate:?} exact={exact} error={error}");
+  assert!(error < 1.0e-10 * exact.abs());
+}
+
+#[test]
+fn test_differentiate_ridders_against_finite_difference() {
+  use crate::utils::differentiate::{differentiate_finite_difference_adaptive, Stencil};
+
+  let f = |x: f64| (x*x).atan() * x.cosh();
+  let x = 0.8;
+
+  let ridders = differentiate_ridders(x, 0.2, f);
+  let central = differentiate_finite_difference_adaptive(x, 1, 2, Stencil::Central, f);
+  println!("ridders={ridders:?} central={central:?}");
+  assert!(ridders.error < central.error);
+  assert!((ridders.value - central.value).abs() < central.error * 10.0);
+}
@@ -0,0 +1,20 @@
+pub mod differentiate_complex_step;
+pub mod differentiate_dual;
+pub mod differentiate_finite_difference;
+pub mod differentiate_grid;
+pub mod differentiate_ridders;
+
+pub use differentiate_complex_step::complex_step_derivative;
+pub use differentiate_dual::differentiate_dual;
+pub use differentiate_finite_difference::differentiate_finite_difference;
+pub use differentiate_finite_difference::differentiate_finite_difference_adaptive;
+pub use differentiate_finite_difference::finite_difference_weights;
+pub use differentiate_finite_difference::Stencil;
+pub use differentiate_finite_difference::stencil_offsets;
+pub use differentiate_grid::curl_2d;
+pub use differentiate_grid::curl_3d;
+pub use differentiate_grid::differentiate_grid;
+pub use differentiate_grid::divergence;
+pub use differentiate_grid::gradient;
+pub use differentiate_grid::laplacian;
+pub use differentiate_ridders::differentiate_ridders;
@@ -0,0 +1,246 @@
+use std::fmt;
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::utils::scalar::Scalar;
+
+// A dual number a + b eps with eps^2 = 0. Evaluating f(x + eps) gives f(x) + f'(x) eps, so carrying
+// the derivative alongside the value through every operation differentiates exactly, without the
+// truncation and round-off trade-off of finite differences.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Dual {
+  pub value: f64,
+  pub derivative: f64,
+}
+
+impl Dual {
+  pub const fn new(value: f64, derivative: f64) -> Dual {
+    Dual { value, derivative }
+  }
+
+  // The independent variable we are differentiating with respect to, dx/dx = 1.
+  pub const fn variable(value: f64) -> Dual {
+    Dual::new(value, 1.0)
+  }
+
+  pub const fn constant(value: f64) -> Dual {
+    Dual::new(value, 0.0)
+  }
+
+  // The chain rule: f(a + b eps) = f(a) + f'(a) b eps.
+  fn chain(self, f: f64, df: f64) -> Dual {
+    Dual::new(f, df * self.derivative)
+  }
+}
+
+impl Scalar for Dual {
+  fn exp(self) -> Dual {
+    let e = self.value.exp();
+    self.chain(e, e)
+  }
+
+  fn ln(self) -> Dual {
+    self.chain(self.value.ln(), self.value.recip())
+  }
+
+  fn sqrt(self) -> Dual {
+    let s = self.value.sqrt();
+    self.chain(s, 0.5 / s)
+  }
+
+  fn powi(self, n: i32) -> Dual {
+    if n == 0 {
+      return Dual::constant(1.0);
+    }
+    self.chain(self.value.powi(n), (n as f64) * self.value.powi(n - 1))
+  }
+
+  fn powf(self, p: f64) -> Dual {
+    self.chain(self.value.powf(p), p * self.value.powf(p - 1.0))
+  }
+
+  fn recip(self) -> Dual {
+    let r = self.value.recip();
+    self.chain(r, -r * r)
+  }
+
+  fn sin(self) -> Dual {
+    self.chain(self.value.sin(), self.value.cos())
+  }
+
+  fn cos(self) -> Dual {
+    self.chain(self.value.cos(), -self.value.sin())
+  }
+
+  fn tan(self) -> Dual {
+    let t = self.value.tan();
+    self.chain(t, 1.0 + t * t)
+  }
+
+  fn atan(self) -> Dual {
+    self.chain(self.value.atan(), (1.0 + self.value * self.value).recip())
+  }
+
+  fn sinh(self) -> Dual {
+    self.chain(self.value.sinh(), self.value.cosh())
+  }
+
+  fn cosh(self) -> Dual {
+    self.chain(self.value.cosh(), self.value.sinh())
+  }
+
+  fn tanh(self) -> Dual {
+    let t = self.value.tanh();
+    self.chain(t, 1.0 - t * t)
+  }
+}
+
+impl From<f64> for Dual {
+  fn from(value: f64) -> Dual {
+    Dual::constant(value)
+  }
+}
+
+impl fmt::Display for Dual {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{} + {} eps", self.value, self.derivative)
+  }
+}
+
+impl Neg for Dual {
+  type Output = Dual;
+  fn neg(self) -> Dual {
+    Dual::new(-self.value, -self.derivative)
+  }
+}
+
+impl Add for Dual {
+  type Output = Dual;
+  fn add(self, rhs: Dual) -> Dual {
+    Dual::new(self.value + rhs.value, self.derivative + rhs.derivative)
+  }
+}
+
+impl Sub for Dual {
+  type Output = Dual;
+  fn sub(self, rhs: Dual) -> Dual {
+    Dual::new(self.value - rhs.value, self.derivative - rhs.derivative)
+  }
+}
+
+impl Mul for Dual {
+  type Output = Dual;
+  fn mul(self, rhs: Dual) -> Dual {
+    Dual::new(self.value * rhs.value, self.derivative * rhs.value + self.value * rhs.derivative)
+  }
+}
+
+impl Div for Dual {
+  type Output = Dual;
+  fn div(self, rhs: Dual) -> Dual {
+    let q = self.value / rhs.value;
+    Dual::new(q, (self.derivative - q * rhs.derivative) / rhs.value)
+  }
+}
+
+impl Add<f64> for Dual {
+  type Output = Dual;
+  fn add(self, rhs: f64) -> Dual {
+    Dual::new(self.value + rhs, self.derivative)
+  }
+}
+
+impl Sub<f64> for Dual {
+  type Output = Dual;
+  fn sub(self, rhs: f64) -> Dual {
+    Dual::new(self.value - rhs, self.derivative)
+  }
+}
+
+impl Mul<f64> for Dual {
+  type Output = Dual;
+  fn mul(self, rhs: f64) -> Dual {
+    Dual::new(self.value * rhs, self.derivative * rhs)
+  }
+}
+
+impl Div<f64> for Dual {
+  type Output = Dual;
+  fn div(self, rhs: f64) -> Dual {
+    Dual::new(self.value / rhs, self.derivative / rhs)
+  }
+}
+
+impl Add<Dual> for f64 {
+  type Output = Dual;
+  fn add(self, rhs: Dual) -> Dual {
+    rhs + self
+  }
+}
+
+impl Sub<Dual> for f64 {
+  type Output = Dual;
+  fn sub(self, rhs: Dual) -> Dual {
+    Dual::new(self - rhs.value, -rhs.derivative)
+  }
+}
+
+impl Mul<Dual> for f64 {
+  type Output = Dual;
+  fn mul(self, rhs: Dual) -> Dual {
+    rhs * self
+  }
+}
+
+impl Div<Dual> for f64 {
+  type Output = Dual;
+  fn div(self, rhs: Dual) -> Dual {
+    Dual::constant(self) / rhs
+  }
+}
+
+impl AddAssign for Dual {
+  fn add_assign(&mut self, rhs: Dual) {
+    *self = *self + rhs;
+  }
+}
+
+impl SubAssign for Dual {
+  fn sub_assign(&mut self, rhs: Dual) {
+    *self = *self - rhs;
+  }
+}
+
+impl MulAssign for Dual {
+  fn mul_assign(&mut self, rhs: Dual) {
+    *self = *self * rhs;
+  }
+}
+
+#[test]
+fn test_dual_elementary_functions() {
+  let x = 0.6;
+  let d = Dual::variable(x);
+
+  let cases: Vec<(Dual, f64, f64)> = vec![
+    (d.exp(), x.exp(), x.exp()),
+    (d.ln(), x.ln(), 1.0 / x),
+    (d.sqrt(), x.sqrt(), 0.5 / x.sqrt()),
+    (d.powi(3), x.powi(3), 3.0 * x * x),
+    (d.powf(2.5), x.powf(2.5), 2.5 * x.powf(1.5)),
+    (d.recip(), 1.0 / x, -1.0 / (x * x)),
+    (d.sin(), x.sin(), x.cos()),
+    (d.cos(), x.cos(), -x.sin()),
+    (d.tan(), x.tan(), 1.0 / (x.cos() * x.cos())),
+    (d.atan(), x.atan(), 1.0 / (1.0 + x * x)),
+    (d.sinh(), x.sinh(), x.cosh()),
+    (d.cosh(), x.cosh(), x.sinh()),
+    (d.tanh(), x.tanh(), 1.0 / (x.cosh() * x.cosh())),
+    (d * d / (1.0 - d), x * x / (1.0 - x), (2.0 * x - x * x) / ((1.0 - x) * (1.0 - x))),
+  ];
+
+  for (i, (dual, value, derivative)) in cases.into_iter().enumerate() {
+    println!("{i}: {dual} expected {value} + {derivative} eps");
+    assert!((dual.value - value).abs() < 1.0e-15);
+    assert!((dual.derivative - derivative).abs() < 1.0e-14);
+  }
+}
@@ -0,0 +1,13 @@
+// An approximate value together with an estimate of its absolute error, as returned by the
+// methods which can judge their own accuracy (typically by comparing successive approximations).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Estimate {
+  pub value: f64,
+  pub error: f64,
+}
+
+impl Estimate {
+  pub fn new(value: f64, error: f64) -> Estimate {
+    Estimate { value, error: error.abs() }
+  }
+}
@@ -1,44 +1,20 @@
 use ndarray::Array1;
+#[cfg(test)]
 use std::f64::consts::PI;
 
-fn legendre_polynomial_roots_and_gaussian_quadrature_weights(n: usize) -> (Array1<f64>, Array1<f64>) {
-  let nf64 = n as f64;
-
-  let a = Array1::linspace(3.0, 4.0*nf64 - 1.0, n);
-  let a = a / (4.0*nf64 + 2.0);
-  
-  let t1 = 8.0*nf64*nf64*a.tan();
-  let mut x = (PI*a + 1.0 / t1).cos();
-
-  let epsilon = 1.0e-15;
-  loop {
-    let mut p0 = Array1::ones(n);
-    let mut p1 = x.clone();
-    for k in 1..n {
-      let kf64 = k as f64;
-
-      let p2 = (2.0*kf64 + 1.0)*x.clone()*p1.clone();
-      let p2 = p2 - kf64*p0.clone();
-      let p2 = p2/(kf64 + 1.0);
-
-      p0.assign(&p1);
-      p1.assign(&p2);
-    }
-
-    let dp = p0 - x.clone()*p1.clone();
-    let dp = (nf64 + 1.0)*(dp)/(1.0-x.pow2());
-
-    let dx = p1/dp.clone();
-    let delta = dx.abs().iter().copied().reduce(f64::max).unwrap();
-
-    let xminusdx = x.clone() - dx;
-    x.assign(&xminusdx);
-
-    if delta <= epsilon {
-      let w = 2.0*(nf64+1.0)*(nf64+1.0)/(nf64*nf64*(1.0-x.pow2())*dp.pow2());
-      return (x, w);
-    }
-  }
+use crate::utils::Estimate;
+use crate::utils::scalar::Scalar;
+use crate::utils::special::OrthogonalPolynomial;
+
+// The nodes of the n point Gauss-Legendre rule are the roots of P_n, and the weights are
+//   w_k = 2 / ((1 - x_k^2) P_n'(x_k)^2).
+pub(crate) fn legendre_polynomial_roots_and_gaussian_quadrature_weights(n: usize) -> (Array1<f64>, Array1<f64>) {
+  let x = Array1::from(OrthogonalPolynomial::Legendre.roots(n));
+  let w = x.mapv(|x| {
+    let dp = OrthogonalPolynomial::Legendre.derivative(n, x);
+    2.0 / ((1.0 - x*x) * dp * dp)
+  });
+  (x, w)
 }
 
 pub fn integrate_gaussian_quadrature<F>(a: f64, b: f64, n: usize, f: F) -> f64
@@ -48,20 +24,46 @@ pub fn integrate_gaussian_quadrature<F>(a: f64, b: f64, n: usize, f: F) -> f64
   let xp = 0.5 * (b - a) * x + 0.5 * (a + b);
   let wp = 0.5 * (b - a) * w;
   let fx = xp.map(|&x0| f(x0));
-  return (fx * wp).sum();
+  (fx * wp).sum()
+}
+
+// The same rule for any Scalar type, so that the limits and integrand may be dual numbers carrying
+// derivatives with respect to a parameter.
+pub fn integrate_gaussian_quadrature_scalar<T, F>(a: T, b: T, n: usize, f: F) -> T
+    where T: Scalar, F: Fn(T) -> T
+{
+  let (x, w) = legendre_polynomial_roots_and_gaussian_quadrature_weights(n);
+  let half_width = (b - a) * 0.5;
+  let midpoint = (a + b) * 0.5;
+
+  let mut total = T::from(0.0);
+  for (&xk, &wk) in x.iter().zip(w.iter()) {
+    total = total + f(half_width * xk + midpoint) * wk;
+  }
+
+  total * half_width
 }
 
 pub fn integrate_gaussian_quadrature_adaptive<F>(a: f64, b: f64, epsilon: f64, f: F) -> f64
     where F: Fn(f64) -> f64
+{
+  integrate_gaussian_quadrature_adaptive_with_error(a, b, epsilon, f).value
+}
+
+// Doubles the number of sample points until successive approximations agree to within epsilon,
+// reporting the last difference as the error estimate.
+pub fn integrate_gaussian_quadrature_adaptive_with_error<F>(a: f64, b: f64, epsilon: f64, f: F) -> Estimate
+    where F: Fn(f64) -> f64
 {
   let mut n = 10;
 
   let mut approximation1 = integrate_gaussian_quadrature(a, b, n, &f);
+  let mut error = f64::INFINITY;
 
   while n < 640 {
     n *= 2;
     let approximation2 = integrate_gaussian_quadrature(a, b, n, &f);
-    let error = (approximation2 - approximation1).abs();
+    error = (approximation2 - approximation1).abs();
 
     approximation1 = approximation2;
 
@@ -70,10 +72,11 @@ pub fn integrate_gaussian_quadrature_adaptive<F>(a: f64, b: f64, epsilon: f64, f
     }
   }
 
-  approximation1
+  Estimate::new(approximation1, error)
 }
 
 #[test]
+#[allow(clippy::needless_borrows_for_generic_args)]
 fn test_legendre_polynomial_roots_and_gaussian_quadrature_weights() {
   let f = |x: f64| x*x*x*x - 2.0*x + 1.0;
   let a = 0.0;
@@ -87,6 +90,7 @@ fn test_legendre_polynomial_roots_and_gaussian_quadrature_weights() {
 }
 
 #[test]
+#[allow(clippy::needless_borrows_for_generic_args, clippy::excessive_precision)]
 fn test_integrate_gaussian_quadrature_adaptive() {
   let f = |x: f64| (PI / 2.0 * x*x*x).cos();
   let a = 0.0;
@@ -103,3 +107,16 @@ fn test_integrate_gaussian_quadrature_adaptive() {
     epsilon /= 100.0;
   }
 }
+
+#[test]
+fn test_integrate_gaussian_quadrature_adaptive_with_error() {
+  let f = |x: f64| x.sin();
+  let a = 0.0;
+  let b = PI;
+  let exact = 2.0;
+
+  let estimate = integrate_gaussian_quadrature_adaptive_with_error(a, b, 1.0e-12, f);
+  println!("estimate = {estimate:?}");
+  assert!((estimate.value - exact).abs() < 1.0e-12);
+  assert!(estimate.error < 1.0e-12);
+}
@@ -0,0 +1,241 @@
+use std::f64::consts::PI;
+use std::ops::{Add, Mul};
+
+use crate::utils::complex::Complex;
+use crate::utils::integrate::integrate_gaussian_quadrature::legendre_polynomial_roots_and_gaussian_quadrature_weights;
+
+// A parametric curve r(t) in D dimensions. The parameter runs through the breakpoints in
+// increasing order, and the curve must be smooth between consecutive breakpoints since each piece
+// gets its own Gaussian quadrature. Corners of a polyline are breakpoints, for example.
+pub trait Path<const D: usize> {
+  fn breakpoints(&self) -> Vec<f64>;
+  fn position(&self, t: f64) -> [f64; D];
+  fn velocity(&self, t: f64) -> [f64; D];
+}
+
+// Straight segments joining the vertices in order. Segment i is traversed for t from i to i+1.
+pub struct Polyline<const D: usize> {
+  pub vertices: Vec<[f64; D]>,
+}
+
+impl<const D: usize> Polyline<D> {
+  pub fn new(vertices: Vec<[f64; D]>) -> Polyline<D> {
+    assert!(vertices.len() >= 2, "a polyline needs at least two vertices");
+    Polyline { vertices }
+  }
+
+  // A polyline which returns from the last vertex to the first.
+  pub fn closed(vertices: Vec<[f64; D]>) -> Polyline<D> {
+    let mut vertices = vertices;
+    vertices.push(vertices[0]);
+    Polyline::new(vertices)
+  }
+
+  fn segment(&self, t: f64) -> (usize, f64) {
+    let i = (t.max(0.0).floor() as usize).min(self.vertices.len() - 2);
+    (i, t - i as f64)
+  }
+}
+
+impl<const D: usize> Path<D> for Polyline<D> {
+  fn breakpoints(&self) -> Vec<f64> {
+    (0..self.vertices.len()).map(|i| i as f64).collect()
+  }
+
+  fn position(&self, t: f64) -> [f64; D] {
+    let (i, s) = self.segment(t);
+    let (p0, p1) = (self.vertices[i], self.vertices[i + 1]);
+    std::array::from_fn(|k| p0[k] + s * (p1[k] - p0[k]))
+  }
+
+  fn velocity(&self, t: f64) -> [f64; D] {
+    let (i, _) = self.segment(t);
+    let (p0, p1) = (self.vertices[i], self.vertices[i + 1]);
+    std::array::from_fn(|k| p1[k] - p0[k])
+  }
+}
+
+// A counterclockwise circle in the plane, traversed once for t from 0 to 2 pi. As a contour in
+// the complex plane the center is re + i im.
+pub struct Circle {
+  pub center: [f64; 2],
+  pub radius: f64,
+}
+
+impl Circle {
+  pub fn new(center: [f64; 2], radius: f64) -> Circle {
+    Circle { center, radius }
+  }
+}
+
+impl Path<2> for Circle {
+  fn breakpoints(&self) -> Vec<f64> {
+    // Quarter turns keep the number of oscillations per piece small.
+    vec![0.0, PI / 2.0, PI, 3.0 * PI / 2.0, 2.0 * PI]
+  }
+
+  fn position(&self, t: f64) -> [f64; 2] {
+    [self.center[0] + self.radius * t.cos(), self.center[1] + self.radius * t.sin()]
+  }
+
+  fn velocity(&self, t: f64) -> [f64; 2] {
+    [-self.radius * t.sin(), self.radius * t.cos()]
+  }
+}
+
+// A general smooth curve given by its position r(t) and velocity r'(t) for t from t0 to t1.
+pub struct Curve<R, V> {
+  pub t0: f64,
+  pub t1: f64,
+  pub position: R,
+  pub velocity: V,
+}
+
+impl<R, V> Curve<R, V> {
+  pub fn new(t0: f64, t1: f64, position: R, velocity: V) -> Curve<R, V> {
+    Curve { t0, t1, position, velocity }
+  }
+}
+
+impl<R, V, const D: usize> Path<D> for Curve<R, V>
+  where R: Fn(f64) -> [f64; D], V: Fn(f64) -> [f64; D]
+{
+  fn breakpoints(&self) -> Vec<f64> {
+    vec![self.t0, self.t1]
+  }
+
+  fn position(&self, t: f64) -> [f64; D] {
+    (self.position)(t)
+  }
+
+  fn velocity(&self, t: f64) -> [f64; D] {
+    (self.velocity)(t)
+  }
+}
+
+// Gaussian quadrature with n points on each piece between consecutive breakpoints.
+fn integrate_pieces<T, G>(breakpoints: &[f64], n: usize, zero: T, g: G) -> T
+  where T: Copy + Add<Output = T> + Mul<f64, Output = T>, G: Fn(f64) -> T
+{
+  let (x, w) = legendre_polynomial_roots_and_gaussian_quadrature_weights(n);
+
+  let mut total = zero;
+  for piece in breakpoints.windows(2) {
+    let (a, b) = (piece[0], piece[1]);
+    for (&xk, &wk) in x.iter().zip(w.iter()) {
+      let t = 0.5 * (b - a) * xk + 0.5 * (a + b);
+      total = total + g(t) * (0.5 * (b - a) * wk);
+    }
+  }
+
+  total
+}
+
+fn norm<const D: usize>(v: [f64; D]) -> f64 {
+  v.iter().map(|vk| vk * vk).sum::<f64>().sqrt()
+}
+
+// Integral of the scalar field f along the path with respect to arc length, f(r(t)) |r'(t)| dt.
+pub fn integrate_line_scalar<P, F, const D: usize>(path: &P, n: usize, f: F) -> f64
+  where P: Path<D>, F: Fn([f64; D]) -> f64
+{
+  integrate_pieces(&path.breakpoints(), n, 0.0,
+    |t| f(path.position(t)) * norm(path.velocity(t))
+  )
+}
+
+// Integral of the vector field along the path, F(r(t)) . r'(t) dt, such as the work done by a
+// force moving a particle along the path.
+pub fn integrate_line_vector<P, F, const D: usize>(path: &P, n: usize, field: F) -> f64
+  where P: Path<D>, F: Fn([f64; D]) -> [f64; D]
+{
+  integrate_pieces(&path.breakpoints(), n, 0.0, |t| {
+    let force = field(path.position(t));
+    let velocity = path.velocity(t);
+    force.iter().zip(velocity.iter()).map(|(fk, vk)| fk * vk).sum()
+  })
+}
+
+pub fn arc_length<P, const D: usize>(path: &P, n: usize) -> f64
+  where P: Path<D>
+{
+  integrate_line_scalar(path, n, |_| 1.0)
+}
+
+// Contour integral of f(z) dz along a path in the complex plane, with the point (x, y) of the path
+// standing for z = x + i y.
+pub fn integrate_contour<P, F>(path: &P, n: usize, f: F) -> Complex
+  where P: Path<2>, F: Fn(Complex) -> Complex
+{
+  integrate_pieces(&path.breakpoints(), n, Complex::new(0.0, 0.0), |t| {
+    let [x, y] = path.position(t);
+    let [dx, dy] = path.velocity(t);
+    f(Complex::new(x, y)) * Complex::new(dx, dy)
+  })
+}
+
+#[test]
+fn test_arc_length() {
+  let triangle = Polyline::closed(vec![[0.0, 0.0], [3.0, 0.0], [3.0, 4.0]]);
+  assert!((arc_length(&triangle, 2) - 12.0).abs() < 1.0e-14);
+
+  let circle = Circle::new([1.0, -1.0], 2.0);
+  assert!((arc_length(&circle, 10) - 4.0 * PI).abs() < 1.0e-13);
+
+  // One turn of a helix of unit radius and pitch 2 pi.
+  let helix = Curve::new(0.0, 2.0 * PI,
+    |t: f64| [t.cos(), t.sin(), t],
+    |t: f64| [-t.sin(), t.cos(), 1.0],
+  );
+  let approx = arc_length(&helix, 10);
+  println!("helix length = {approx}");
+  assert!((approx - 2.0 * PI * 2.0_f64.sqrt()).abs() < 1.0e-13);
+}
+
+#[test]
+fn test_integrate_line_scalar() {
+  // The mass of a wire along y = x^2 from (0, 0) to (1, 1) with density x is
+  // integral from 0 to 1 of x sqrt(1 + 4x^2) dx = (5 sqrt(5) - 1)/12.
+  let wire = Curve::new(0.0, 1.0, |t: f64| [t, t*t], |t: f64| [1.0, 2.0*t]);
+  let approx = integrate_line_scalar(&wire, 20, |[x, _]| x);
+  let exact = (5.0 * 5.0_f64.sqrt() - 1.0) / 12.0;
+  println!("approx={approx} exact={exact}");
+  assert!((approx - exact).abs() < 1.0e-13);
+}
+
+#[test]
+fn test_integrate_line_vector() {
+  // The circulation of (-y, x) around a circle of radius R is 2 pi R^2.
+  let circle = Circle::new([0.0, 0.0], 2.0);
+  let approx = integrate_line_vector(&circle, 10, |[x, y]| [-y, x]);
+  assert!((approx - 8.0 * PI).abs() < 1.0e-12);
+
+  // A conservative field does no work around a closed path.
+  let path = Polyline::closed(vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [1.0, 2.0, 0.0], [0.0, 1.0, 3.0]]);
+  let approx = integrate_line_vector(&path, 10, |[x, y, z]| [y*z, x*z, x*y]);
+  println!("work = {approx}");
+  assert!(approx.abs() < 1.0e-13);
+}
+
+#[test]
+fn test_integrate_contour() {
+  let two_pi_i = Complex::new(0.0, 2.0 * PI);
+
+  // Residue of 1/z at 0 is 1.
+  let circle = Circle::new([0.0, 0.0], 1.0);
+  let approx = integrate_contour(&circle, 20, |z| z.recip());
+  assert!((approx - two_pi_i).abs() < 1.0e-13);
+
+  // Residue of e^z/z^3 at 0 is 1/2.
+  let approx = integrate_contour(&circle, 20, |z| z.exp() / (z * z * z));
+  println!("approx = {approx}");
+  assert!((approx - two_pi_i * 0.5).abs() < 1.0e-12);
+
+  // Residue of 1/(z - 1/2) around a square enclosing 1/2, and nothing from an entire function.
+  let square = Polyline::closed(vec![[-1.0, -1.0], [1.0, -1.0], [1.0, 1.0], [-1.0, 1.0]]);
+  let approx = integrate_contour(&square, 40, |z| (z - 0.5).recip());
+  println!("approx = {approx}");
+  assert!((approx - two_pi_i).abs() < 1.0e-8);
+  let approx = integrate_contour(&square, 10, |z| z * z);
+  assert!(approx.abs() < 1.0e-14);
+}
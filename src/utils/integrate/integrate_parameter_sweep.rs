@@ -0,0 +1,139 @@
+use ndarray::Array1;
+
+use crate::utils::Estimate;
+use crate::utils::integrate::integrate_gaussian_quadrature::legendre_polynomial_roots_and_gaussian_quadrature_weights;
+
+// Integrating a family f(x; p) for many values of p with separate calls to
+// integrate_gaussian_quadrature recomputes the Legendre roots and weights every time, which
+// dominates the cost for smooth integrands. These functions compute the sample points and weights
+// once and reuse them for every parameter value.
+//
+// The integrand is called as f(x, p). When the limits of integration depend on the parameter too,
+// substitute x = a(p) + (b(p) - a(p)) s and integrate over s from 0 to 1 instead.
+
+struct ScaledRule {
+  x: Array1<f64>,
+  w: Array1<f64>,
+}
+
+impl ScaledRule {
+  fn new(a: f64, b: f64, n: usize) -> ScaledRule {
+    let (x, w) = legendre_polynomial_roots_and_gaussian_quadrature_weights(n);
+    let x = 0.5 * (b - a) * x + 0.5 * (a + b);
+    let w = 0.5 * (b - a) * w;
+    ScaledRule { x, w }
+  }
+
+  fn integrate<F>(&self, p: f64, f: &F) -> f64
+    where F: Fn(f64, f64) -> f64
+  {
+    self.x.iter().zip(self.w.iter()).map(|(&x, &w)| w * f(x, p)).sum()
+  }
+}
+
+// Integral from a to b of f(x, p) dx with n point Gaussian quadrature, for each p in parameters.
+pub fn integrate_parameter_sweep<F>(a: f64, b: f64, n: usize, parameters: &Array1<f64>, f: F) -> Array1<f64>
+  where F: Fn(f64, f64) -> f64
+{
+  let rule = ScaledRule::new(a, b, n);
+
+  parameters.map(|&p| rule.integrate(p, &f))
+}
+
+// As integrate_parameter_sweep, but each value is taken from a 2n point rule and its error is
+// estimated by the difference from the n point rule.
+pub fn integrate_parameter_sweep_with_error<F>(a: f64, b: f64, n: usize, parameters: &Array1<f64>, f: F) -> Array1<Estimate>
+  where F: Fn(f64, f64) -> f64
+{
+  let coarse = ScaledRule::new(a, b, n);
+  let fine = ScaledRule::new(a, b, 2*n);
+
+  parameters.map(|&p| estimate_with_rules(&coarse, &fine, p, &f))
+}
+
+// As integrate_parameter_sweep_with_error, but the parameter values are divided between as many
+// threads as the machine has available.
+pub fn integrate_parameter_sweep_parallel<F>(a: f64, b: f64, n: usize, parameters: &Array1<f64>, f: F) -> Array1<Estimate>
+  where F: Fn(f64, f64) -> f64 + Sync
+{
+  let coarse = ScaledRule::new(a, b, n);
+  let fine = ScaledRule::new(a, b, 2*n);
+
+  let parameters = parameters.to_vec();
+  let mut results = vec![Estimate::new(0.0, 0.0); parameters.len()];
+
+  let threads = std::thread::available_parallelism().map_or(1, |threads| threads.get());
+  let chunk_size = parameters.len().div_ceil(threads).max(1);
+
+  std::thread::scope(|scope| {
+    for (ps, rs) in parameters.chunks(chunk_size).zip(results.chunks_mut(chunk_size)) {
+      let coarse = &coarse;
+      let fine = &fine;
+      let f = &f;
+      scope.spawn(move || {
+        for (&p, r) in ps.iter().zip(rs.iter_mut()) {
+          *r = estimate_with_rules(coarse, fine, p, f);
+        }
+      });
+    }
+  });
+
+  Array1::from_vec(results)
+}
+
+fn estimate_with_rules<F>(coarse: &ScaledRule, fine: &ScaledRule, p: f64, f: &F) -> Estimate
+  where F: Fn(f64, f64) -> f64
+{
+  let approximation1 = coarse.integrate(p, f);
+  let approximation2 = fine.integrate(p, f);
+
+  Estimate::new(approximation2, approximation2 - approximation1)
+}
+
+#[test]
+fn test_integrate_parameter_sweep() {
+  use crate::utils::integrate::integrate_gaussian_quadrature;
+
+  // Integral from 0 to 1 of e^(p x) dx = (e^p - 1)/p
+  let f = |x: f64, p: f64| (p * x).exp();
+  let parameters = Array1::linspace(0.5, 5.0, 10);
+
+  let sweep = integrate_parameter_sweep(0.0, 1.0, 20, &parameters, f);
+  for (&p, &approx) in parameters.iter().zip(sweep.iter()) {
+    let exact = (p.exp() - 1.0) / p;
+    let separate = integrate_gaussian_quadrature(0.0, 1.0, 20, |x| f(x, p));
+    println!("p={p} approx={approx} exact={exact}");
+    assert!((approx - exact).abs() < 1.0e-12);
+    assert!((approx - separate).abs() < 1.0e-13);
+  }
+}
+
+#[test]
+fn test_integrate_parameter_sweep_with_error() {
+  // Integral from 0 to pi of cos(m theta - x sin(theta)) dtheta / pi = J_m(x), here J_0(x)
+  let f = |theta: f64, x: f64| (-x * theta.sin()).cos() / std::f64::consts::PI;
+  let parameters = Array1::from_vec(vec![0.0, 1.0, 2.404_825_557_695_773, 10.0]);
+  let expected = [1.0, 0.765_197_686_557_966_6, 0.0, -0.245_935_764_451_348_3];
+
+  let sweep = integrate_parameter_sweep_with_error(0.0, std::f64::consts::PI, 20, &parameters, f);
+  for (estimate, exact) in sweep.iter().zip(expected) {
+    println!("estimate={estimate:?} exact={exact}");
+    assert!((estimate.value - exact).abs() < 1.0e-12);
+    assert!(estimate.error < 1.0e-6);
+  }
+}
+
+#[test]
+fn test_integrate_parameter_sweep_parallel() {
+  let f = |x: f64, p: f64| (p * x).sin();
+  let parameters = Array1::linspace(1.0, 4.0, 101);
+
+  let serial = integrate_parameter_sweep_with_error(0.0, 1.0, 10, &parameters, f);
+  let parallel = integrate_parameter_sweep_parallel(0.0, 1.0, 10, &parameters, f);
+  assert_eq!(serial, parallel);
+
+  for (&p, estimate) in parameters.iter().zip(parallel.iter()) {
+    let exact = (1.0 - p.cos()) / p;
+    assert!((estimate.value - exact).abs() < 1.0e-12);
+  }
+}
@@ -0,0 +1,85 @@
+use crate::utils::Estimate;
+use crate::utils::integrate::integrate_gaussian_quadrature::integrate_gaussian_quadrature_adaptive_with_error;
+
+// Cauchy principal value of the integral from a to b of f(x)/(x-c) dx for a < c < b.
+//
+// The interval is split into a part symmetric about the pole, [c-d, c+d] where d is the distance
+// from c to the nearer endpoint, and whatever remains on the far side. On the symmetric part the
+// two halves are folded onto each other,
+//
+//   PV integral from c-d to c+d of f(x)/(x-c) dx = integral from 0 to d of (f(c+t) - f(c-t))/t dt
+//
+// which has a removable singularity at t = 0 that Gaussian quadrature never samples. On the
+// remaining part we substitute x - c = +/- e^u so that dx/(x-c) = du,
+//
+//   integral from c+d to b of f(x)/(x-c) dx = integral from ln(d) to ln(b-c) of f(c+e^u) du
+//
+// which stays smooth even when c is very close to an endpoint and d is tiny compared to b-a.
+pub fn integrate_principal_value<F>(a: f64, b: f64, c: f64, epsilon: f64, f: F) -> Estimate
+  where F: Fn(f64) -> f64
+{
+  assert!(a < c && c < b, "the pole c={c} must lie strictly between a={a} and b={b}");
+
+  let left = c - a;
+  let right = b - c;
+  let d = left.min(right);
+
+  let symmetric = integrate_gaussian_quadrature_adaptive_with_error(0.0, d, epsilon / 2.0,
+    |t| (f(c + t) - f(c - t)) / t
+  );
+
+  let remainder = if right > left {
+    integrate_gaussian_quadrature_adaptive_with_error(d.ln(), right.ln(), epsilon / 2.0,
+      |u| f(c + u.exp())
+    )
+  } else if left > right {
+    let estimate = integrate_gaussian_quadrature_adaptive_with_error(d.ln(), left.ln(), epsilon / 2.0,
+      |u| f(c - u.exp())
+    );
+    Estimate::new(-estimate.value, estimate.error)
+  } else {
+    Estimate::new(0.0, 0.0)
+  };
+
+  Estimate::new(symmetric.value + remainder.value, symmetric.error + remainder.error)
+}
+
+#[test]
+fn test_integrate_principal_value_constant() {
+  // PV integral from 0 to 1 of 1/(x-c) dx = ln((1-c)/c)
+  for c in [0.5_f64, 0.25, 0.9, 1.0e-3, 1.0e-8, 1.0 - 1.0e-8] {
+    let exact = ((1.0 - c) / c).ln();
+    let estimate = integrate_principal_value(0.0, 1.0, c, 1.0e-12, |_| 1.0);
+    println!("c={c} estimate={estimate:?} exact={exact}");
+    assert!((estimate.value - exact).abs() < 1.0e-10);
+  }
+}
+
+#[test]
+fn test_integrate_principal_value_linear_near_endpoint() {
+  // PV integral from 0 to 1 of x/(x-c) dx = 1 + c ln((1-c)/c)
+  let c: f64 = 1.0e-9;
+  let exact = 1.0 + c * ((1.0 - c) / c).ln();
+  let estimate = integrate_principal_value(0.0, 1.0, c, 1.0e-12, |x| x);
+  println!("estimate={estimate:?} exact={exact}");
+  assert!((estimate.value - exact).abs() < 1.0e-10);
+}
+
+#[test]
+fn test_integrate_principal_value_exponential() {
+  // PV integral from -1 to 1 of e^x/x dx = Ei(1) - Ei(-1) = 2 Shi(1)
+  let exact = 2.0 * 1.057_250_875_375_728_5;
+  let estimate = integrate_principal_value(-1.0, 1.0, 0.0, 1.0e-12, |x| x.exp());
+  println!("estimate={estimate:?} exact={exact}");
+  assert!((estimate.value - exact).abs() < 1.0e-12);
+  assert!(estimate.error < 1.0e-12);
+
+  // And off center, against the subtraction formula evaluated with the same value.
+  let c: f64 = 0.3;
+  let estimate = integrate_principal_value(-1.0, 1.0, c, 1.0e-12, |x| x.exp());
+  let subtracted = integrate_gaussian_quadrature_adaptive_with_error(-1.0, 1.0, 1.0e-14,
+    |x| (x.exp() - c.exp()) / (x - c)
+  ).value + c.exp() * ((1.0 - c) / (1.0 + c)).ln();
+  println!("estimate={estimate:?} subtracted={subtracted}");
+  assert!((estimate.value - subtracted).abs() < 1.0e-10);
+}
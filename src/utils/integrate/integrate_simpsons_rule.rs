@@ -80,6 +80,7 @@ pub fn integrate_simpsons_rule_adaptive<F>(a: f64, b: f64, epsilon: f64, f: F) -
 }
 
 #[test]
+#[allow(clippy::needless_borrows_for_generic_args)]
 fn test_integrate_simpsons_rule_adaptive() {
   let f = |x: f64| x*x*x*x - 2.0*x + 1.0;
   let a = 0.0;
@@ -0,0 +1,256 @@
+use std::f64::consts::PI;
+
+use crate::utils::integrate::integrate_gaussian_quadrature::legendre_polynomial_roots_and_gaussian_quadrature_weights;
+
+// Integrals over the unit sphere of f(theta, phi) dOmega, where theta is the polar angle from the
+// z axis and phi the azimuthal angle, and dOmega = sin(theta) dtheta dphi.
+
+// A Gauss-Legendre rule with n points in cos(theta) times the trapezoidal rule with 2n points in
+// phi. It integrates spherical harmonics exactly up to degree 2n-1, using 2n^2 points.
+pub fn integrate_sphere_product<F>(n: usize, f: F) -> f64
+  where F: Fn(f64, f64) -> f64
+{
+  let (x, w) = legendre_polynomial_roots_and_gaussian_quadrature_weights(n);
+  let m = 2*n;
+  let dphi = 2.0 * PI / (m as f64);
+
+  let mut total = 0.0;
+  for (&cos_theta, &weight) in x.iter().zip(w.iter()) {
+    let theta = cos_theta.acos();
+    let mut ring = 0.0;
+    for j in 0..m {
+      ring += f(theta, dphi * (j as f64));
+    }
+    total += weight * ring;
+  }
+
+  total * dphi
+}
+
+// Lebedev rules are invariant under the rotations and reflections of the octahedron, which makes
+// them about a third cheaper than product rules of the same degree. The points are built from
+// orbits of the octahedral group, each sharing one weight:
+//
+//   a1: (1, 0, 0) and its permutations and sign changes, 6 points
+//   a2: (0, 1, 1)/sqrt(2), 12 points
+//   a3: (1, 1, 1)/sqrt(3), 8 points
+//   b(l): (l, l, m) with m = sqrt(1 - 2l^2), 24 points
+//   c(p): (p, q, 0) with q = sqrt(1 - p^2), 24 points
+//
+// See V. I. Lebedev, "Quadratures on a sphere", USSR Computational Mathematics and Mathematical
+// Physics 16 (1976).
+pub const LEBEDEV_DEGREES: [usize; 5] = [3, 5, 7, 9, 11];
+
+// The points and weights of the Lebedev rule exact for polynomials of the given degree, which must
+// be one of LEBEDEV_DEGREES. The weights sum to one.
+pub fn lebedev_rule(degree: usize) -> Vec<([f64; 3], f64)> {
+  let mut rule = Vec::new();
+
+  match degree {
+    3 => {
+      orbit_a1(&mut rule, 1.0 / 6.0);
+    },
+    5 => {
+      orbit_a1(&mut rule, 1.0 / 15.0);
+      orbit_a3(&mut rule, 3.0 / 40.0);
+    },
+    7 => {
+      orbit_a1(&mut rule, 1.0 / 21.0);
+      orbit_a2(&mut rule, 4.0 / 105.0);
+      orbit_a3(&mut rule, 9.0 / 280.0);
+    },
+    9 => {
+      orbit_a1(&mut rule, 1.0 / 105.0);
+      orbit_a3(&mut rule, 9.0 / 280.0);
+      orbit_c(&mut rule, 0.459_700_843_380_983_1, 1.0 / 35.0);
+    },
+    11 => {
+      orbit_a1(&mut rule, 4.0 / 315.0);
+      orbit_a2(&mut rule, 64.0 / 2835.0);
+      orbit_a3(&mut rule, 27.0 / 1280.0);
+      orbit_b(&mut rule, 1.0 / 11.0_f64.sqrt(), 14641.0 / 725760.0);
+    },
+    _ => panic!("no Lebedev rule of degree {degree}, choose one of {LEBEDEV_DEGREES:?}"),
+  }
+
+  rule
+}
+
+fn orbit_a1(rule: &mut Vec<([f64; 3], f64)>, weight: f64) {
+  for k in 0..3 {
+    for sign in [1.0, -1.0] {
+      let mut point = [0.0; 3];
+      point[k] = sign;
+      rule.push((point, weight));
+    }
+  }
+}
+
+fn orbit_a2(rule: &mut Vec<([f64; 3], f64)>, weight: f64) {
+  let a = 0.5_f64.sqrt();
+  for k in 0..3 {
+    for (s1, s2) in [(1.0, 1.0), (1.0, -1.0), (-1.0, 1.0), (-1.0, -1.0)] {
+      let mut point = [0.0; 3];
+      point[(k + 1) % 3] = s1 * a;
+      point[(k + 2) % 3] = s2 * a;
+      rule.push((point, weight));
+    }
+  }
+}
+
+fn orbit_a3(rule: &mut Vec<([f64; 3], f64)>, weight: f64) {
+  let a = (1.0_f64 / 3.0).sqrt();
+  for sx in [1.0, -1.0] {
+    for sy in [1.0, -1.0] {
+      for sz in [1.0, -1.0] {
+        rule.push(([sx * a, sy * a, sz * a], weight));
+      }
+    }
+  }
+}
+
+fn orbit_b(rule: &mut Vec<([f64; 3], f64)>, l: f64, weight: f64) {
+  let m = (1.0 - 2.0 * l * l).sqrt();
+  for k in 0..3 {
+    for sx in [1.0, -1.0] {
+      for sy in [1.0, -1.0] {
+        for sz in [1.0, -1.0] {
+          let signs = [sx, sy, sz];
+          let point = std::array::from_fn(|i| signs[i] * if i == k { m } else { l });
+          rule.push((point, weight));
+        }
+      }
+    }
+  }
+}
+
+fn orbit_c(rule: &mut Vec<([f64; 3], f64)>, p: f64, weight: f64) {
+  let q = (1.0 - p * p).sqrt();
+  for k in 0..3 {
+    for (a, b) in [(p, q), (q, p)] {
+      for (s1, s2) in [(1.0, 1.0), (1.0, -1.0), (-1.0, 1.0), (-1.0, -1.0)] {
+        let mut point = [0.0; 3];
+        point[(k + 1) % 3] = s1 * a;
+        point[(k + 2) % 3] = s2 * b;
+        rule.push((point, weight));
+      }
+    }
+  }
+}
+
+pub fn integrate_sphere_lebedev<F>(degree: usize, f: F) -> f64
+  where F: Fn(f64, f64) -> f64
+{
+  let total: f64 = lebedev_rule(degree).iter().map(|&([x, y, z], weight)| {
+    let theta = z.clamp(-1.0, 1.0).acos();
+    let phi = y.atan2(x);
+    weight * f(theta, phi)
+  }).sum();
+
+  4.0 * PI * total
+}
+
+// The real spherical harmonics up to l = 3, written out, for testing the rules above.
+#[cfg(test)]
+fn real_spherical_harmonics(theta: f64, phi: f64) -> Vec<(usize, f64)> {
+  let (x, y, z) = (theta.sin() * phi.cos(), theta.sin() * phi.sin(), theta.cos());
+  let c0 = (1.0 / (4.0 * PI)).sqrt();
+  let c1 = (3.0 / (4.0 * PI)).sqrt();
+  let c2 = (15.0 / (4.0 * PI)).sqrt();
+  let c20 = (5.0 / (16.0 * PI)).sqrt();
+  let c3 = (35.0 / (32.0 * PI)).sqrt();
+  let c31 = (21.0 / (32.0 * PI)).sqrt();
+  let c32 = (105.0 / (4.0 * PI)).sqrt();
+  let c30 = (7.0 / (16.0 * PI)).sqrt();
+
+  vec![
+    (0, c0),
+    (1, c1 * x), (1, c1 * y), (1, c1 * z),
+    (2, c2 * x * y), (2, c2 * y * z), (2, c20 * (3.0 * z * z - 1.0)), (2, c2 * x * z),
+    (2, c2 / 2.0 * (x * x - y * y)),
+    (3, c3 * y * (3.0 * x * x - y * y)), (3, c32 * x * y * z), (3, c31 * y * (5.0 * z * z - 1.0)),
+    (3, c30 * z * (5.0 * z * z - 3.0)), (3, c31 * x * (5.0 * z * z - 1.0)),
+    (3, c32 / 2.0 * z * (x * x - y * y)), (3, c3 * x * (x * x - 3.0 * y * y)),
+  ]
+}
+
+#[cfg(test)]
+fn check_orthonormality<I>(degree: usize, integrate: I)
+  where I: Fn(&dyn Fn(f64, f64) -> f64) -> f64
+{
+  let count = real_spherical_harmonics(0.0, 0.0).len();
+  for i in 0..count {
+    for j in 0..count {
+      let (li, _) = real_spherical_harmonics(0.0, 0.0)[i];
+      let (lj, _) = real_spherical_harmonics(0.0, 0.0)[j];
+      if li + lj > degree {
+        continue;
+      }
+
+      let integral = integrate(&|theta, phi| {
+        let ys = real_spherical_harmonics(theta, phi);
+        ys[i].1 * ys[j].1
+      });
+      let expected = if i == j { 1.0 } else { 0.0 };
+      assert!((integral - expected).abs() < 1.0e-13, "degree {degree}: <Y{i}, Y{j}> = {integral}");
+    }
+  }
+}
+
+#[test]
+fn test_lebedev_rule_monomials() {
+  // The integral of x^a y^b z^c over the sphere is zero unless a, b, and c are all even, in which
+  // case it is 4 pi (a-1)!! (b-1)!! (c-1)!! / (a+b+c+1)!!.
+  let double_factorial = |n: i32| (1..=n).rev().step_by(2).map(|k| k as f64).product::<f64>();
+
+  for degree in LEBEDEV_DEGREES {
+    let rule = lebedev_rule(degree);
+    let total_weight: f64 = rule.iter().map(|(_, w)| w).sum();
+    assert!((total_weight - 1.0).abs() < 1.0e-15);
+
+    for a in 0..=degree as i32 {
+      for b in 0..=degree as i32 - a {
+        for c in 0..=degree as i32 - a - b {
+          let approx: f64 = 4.0 * PI * rule.iter()
+            .map(|&([x, y, z], w)| w * x.powi(a) * y.powi(b) * z.powi(c))
+            .sum::<f64>();
+          let exact = if a % 2 == 0 && b % 2 == 0 && c % 2 == 0 {
+            4.0 * PI * double_factorial(a - 1) * double_factorial(b - 1) * double_factorial(c - 1)
+              / double_factorial(a + b + c + 1)
+          } else {
+            0.0
+          };
+          assert!((approx - exact).abs() < 1.0e-14, "degree {degree}: x^{a} y^{b} z^{c} gave {approx}, expected {exact}");
+        }
+      }
+    }
+  }
+}
+
+#[test]
+fn test_integrate_sphere_lebedev() {
+  for degree in LEBEDEV_DEGREES {
+    check_orthonormality(degree, |f| integrate_sphere_lebedev(degree, f));
+  }
+
+  // The solid angle of the upper hemisphere is not smooth, so no rule gets it right, but the degree
+  // 11 rule must weigh exactly the points above the equator: the pole, 4 of the 12 edge midpoints,
+  // 4 of the 8 cube corners and 12 of the 24 (l, l, m) points.
+  let approx = integrate_sphere_lebedev(11, |theta, _| if theta < PI / 2.0 { 1.0 } else { 0.0 });
+  let expected = 4.0 * PI * (4.0 / 315.0 + 4.0 * 64.0 / 2835.0 + 4.0 * 27.0 / 1280.0 + 12.0 * 14641.0 / 725760.0);
+  println!("hemisphere = {approx}, expected {expected}");
+  assert!((approx - expected).abs() < 1.0e-14);
+}
+
+#[test]
+fn test_integrate_sphere_product() {
+  for n in 1..=4 {
+    check_orthonormality(2*n - 1, |f| integrate_sphere_product(n, f));
+  }
+
+  // The integral of e^x over the sphere is 4 pi sinh(1).
+  let approx = integrate_sphere_product(10, |theta, phi| (theta.sin() * phi.cos()).exp());
+  let exact = 4.0 * PI * 1.0_f64.sinh();
+  println!("approx={approx} exact={exact}");
+  assert!((approx - exact).abs() < 1.0e-13);
+}
@@ -114,6 +114,7 @@ pub fn integrate_romberg_adaptive<F>(a: f64, b: f64, epsilon: f64, f: F) -> f64
 }
 
 #[test]
+#[allow(clippy::needless_borrows_for_generic_args)]
 fn test_integrate_trapezoidal_rule_adaptive() {
   let f = |x: f64| x*x*x*x - 2.0*x + 1.0;
   let a = 0.0;
@@ -130,6 +131,7 @@ fn test_integrate_trapezoidal_rule_adaptive() {
 }
 
 #[test]
+#[allow(clippy::needless_borrows_for_generic_args)]
 fn test_integrate_romberg_adaptive() {
   let f = |x: f64| x*x*x*x - 2.0*x + 1.0;
   let a = 0.0;
@@ -1,11 +1,27 @@
 pub mod integrate_gaussian_quadrature;
+pub mod integrate_line;
+pub mod integrate_parameter_sweep;
+pub mod integrate_principal_value;
 pub mod integrate_simpsons_rule;
+pub mod integrate_sphere;
 pub mod integrate_trapezoidal_rule;
 
 pub use integrate_gaussian_quadrature::integrate_gaussian_quadrature;
 pub use integrate_gaussian_quadrature::integrate_gaussian_quadrature_adaptive;
+pub use integrate_gaussian_quadrature::integrate_gaussian_quadrature_adaptive_with_error;
+pub use integrate_gaussian_quadrature::integrate_gaussian_quadrature_scalar;
+pub use integrate_line::arc_length;
+pub use integrate_line::integrate_contour;
+pub use integrate_line::integrate_line_scalar;
+pub use integrate_line::integrate_line_vector;
+pub use integrate_parameter_sweep::integrate_parameter_sweep;
+pub use integrate_parameter_sweep::integrate_parameter_sweep_parallel;
+pub use integrate_parameter_sweep::integrate_parameter_sweep_with_error;
+pub use integrate_principal_value::integrate_principal_value;
 pub use integrate_simpsons_rule::integrate_simpsons_rule;
 pub use integrate_simpsons_rule::integrate_simpsons_rule_adaptive;
+pub use integrate_sphere::integrate_sphere_lebedev;
+pub use integrate_sphere::integrate_sphere_product;
 pub use integrate_trapezoidal_rule::integrate_romberg_adaptive;
 pub use integrate_trapezoidal_rule::integrate_trapezoidal_rule;
 pub use integrate_trapezoidal_rule::integrate_trapezoidal_rule_adaptive;
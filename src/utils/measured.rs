@@ -0,0 +1,499 @@
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::utils::constants::PhysicalConstant;
+use crate::utils::estimate::Estimate;
+
+// A value with a standard uncertainty, propagated to first order through arithmetic and the
+// elementary functions.
+//
+// Rather than the uncertainty alone, a Measured keeps its sensitivity to each independent source of
+// error it was computed from, and the uncertainty is the root sum of squares of those terms. This is
+// what tracks correlations: x - x is exactly 0 with no uncertainty, and x * x has twice the relative
+// uncertainty of x, not sqrt(2) times as adding independent errors in quadrature would give. Call
+// decorrelated() to forget where a value came from and treat it as a fresh measurement.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Measured {
+  pub value: f64,
+  // Sensitivities d(value)/d(source) times the source's uncertainty, sorted by source.
+  terms: Vec<(Source, f64)>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Source {
+  Independent(u64),
+  // A physical constant is the same source of error every time it is used, so it is identified by
+  // its name rather than given a fresh id on each conversion. Correlations between different
+  // constants from the CODATA adjustment are not tracked.
+  Constant(&'static str),
+}
+
+static NEXT_SOURCE: AtomicU64 = AtomicU64::new(0);
+
+impl Measured {
+  // An independent measurement.
+  pub fn new(value: f64, uncertainty: f64) -> Measured {
+    let source = Source::Independent(NEXT_SOURCE.fetch_add(1, Ordering::Relaxed));
+    Measured::with_source(value, uncertainty, source)
+  }
+
+  // A value known exactly.
+  pub fn exact(value: f64) -> Measured {
+    Measured { value, terms: Vec::new() }
+  }
+
+  fn with_source(value: f64, uncertainty: f64, source: Source) -> Measured {
+    if uncertainty == 0.0 {
+      return Measured::exact(value);
+    }
+    Measured { value, terms: vec![(source, uncertainty.abs())] }
+  }
+
+  pub fn uncertainty(&self) -> f64 {
+    self.terms.iter().fold(0.0, |sum, (_, term)| sum + term * term).sqrt()
+  }
+
+  pub fn relative_uncertainty(&self) -> f64 {
+    (self.uncertainty() / self.value).abs()
+  }
+
+  // The same value and uncertainty as an independent measurement.
+  pub fn decorrelated(&self) -> Measured {
+    Measured::new(self.value, self.uncertainty())
+  }
+
+  // The covariance of two values, from the sources of error they share.
+  pub fn covariance(&self, other: &Measured) -> f64 {
+    let mut covariance = 0.0;
+    let (mut i, mut j) = (0, 0);
+    while i < self.terms.len() && j < other.terms.len() {
+      match self.terms[i].0.cmp(&other.terms[j].0) {
+        std::cmp::Ordering::Less => i += 1,
+        std::cmp::Ordering::Greater => j += 1,
+        std::cmp::Ordering::Equal => {
+          covariance += self.terms[i].1 * other.terms[j].1;
+          i += 1;
+          j += 1;
+        }
+      }
+    }
+    covariance
+  }
+
+  pub fn correlation(&self, other: &Measured) -> f64 {
+    self.covariance(other) / (self.uncertainty() * other.uncertainty())
+  }
+
+  // f(a) to first order, given f(a) and f'(a). Terms which come out exactly zero are dropped, here
+  // and in combine, so that x - x is the same as an exact 0 and dead terms don't pile up.
+  fn chain(&self, f: f64, df: f64) -> Measured {
+    let terms = self.terms.iter().map(|&(source, term)| (source, df * term)).filter(|&(_, term)| term != 0.0).collect();
+    Measured { value: f, terms }
+  }
+
+  // f(a, b) to first order, given f(a, b) and its partial derivatives.
+  fn combine(a: &Measured, b: &Measured, f: f64, df_da: f64, df_db: f64) -> Measured {
+    let mut terms = Vec::with_capacity(a.terms.len() + b.terms.len());
+    let (mut i, mut j) = (0, 0);
+    while i < a.terms.len() || j < b.terms.len() {
+      let order = match (a.terms.get(i), b.terms.get(j)) {
+        (Some(x), Some(y)) => x.0.cmp(&y.0),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        _ => std::cmp::Ordering::Greater,
+      };
+      match order {
+        std::cmp::Ordering::Less => {
+          terms.push((a.terms[i].0, df_da * a.terms[i].1));
+          i += 1;
+        }
+        std::cmp::Ordering::Greater => {
+          terms.push((b.terms[j].0, df_db * b.terms[j].1));
+          j += 1;
+        }
+        std::cmp::Ordering::Equal => {
+          terms.push((a.terms[i].0, df_da * a.terms[i].1 + df_db * b.terms[j].1));
+          i += 1;
+          j += 1;
+        }
+      }
+    }
+    terms.retain(|&(_, term)| term != 0.0);
+    Measured { value: f, terms }
+  }
+
+  pub fn exp(&self) -> Measured {
+    let e = self.value.exp();
+    self.chain(e, e)
+  }
+
+  pub fn ln(&self) -> Measured {
+    self.chain(self.value.ln(), self.value.recip())
+  }
+
+  pub fn sqrt(&self) -> Measured {
+    let s = self.value.sqrt();
+    self.chain(s, 0.5 / s)
+  }
+
+  pub fn powi(&self, n: i32) -> Measured {
+    if n == 0 {
+      return Measured::exact(1.0);
+    }
+    self.chain(self.value.powi(n), (n as f64) * self.value.powi(n - 1))
+  }
+
+  pub fn powf(&self, p: f64) -> Measured {
+    self.chain(self.value.powf(p), p * self.value.powf(p - 1.0))
+  }
+
+  pub fn recip(&self) -> Measured {
+    let r = self.value.recip();
+    self.chain(r, -r * r)
+  }
+
+  pub fn sin(&self) -> Measured {
+    self.chain(self.value.sin(), self.value.cos())
+  }
+
+  pub fn cos(&self) -> Measured {
+    self.chain(self.value.cos(), -self.value.sin())
+  }
+
+  pub fn tan(&self) -> Measured {
+    let t = self.value.tan();
+    self.chain(t, 1.0 + t * t)
+  }
+
+  pub fn atan(&self) -> Measured {
+    self.chain(self.value.atan(), 1.0 / (1.0 + self.value * self.value))
+  }
+
+  pub fn sinh(&self) -> Measured {
+    self.chain(self.value.sinh(), self.value.cosh())
+  }
+
+  pub fn cosh(&self) -> Measured {
+    self.chain(self.value.cosh(), self.value.sinh())
+  }
+
+  pub fn tanh(&self) -> Measured {
+    let t = self.value.tanh();
+    self.chain(t, 1.0 - t * t)
+  }
+}
+
+// An error estimate is taken as the standard uncertainty.
+impl From<Estimate> for Measured {
+  fn from(estimate: Estimate) -> Measured {
+    Measured::new(estimate.value, estimate.error)
+  }
+}
+
+impl From<PhysicalConstant> for Measured {
+  fn from(constant: PhysicalConstant) -> Measured {
+    Measured::with_source(constant.value, constant.uncertainty, Source::Constant(constant.name))
+  }
+}
+
+impl From<f64> for Measured {
+  fn from(value: f64) -> Measured {
+    Measured::exact(value)
+  }
+}
+
+impl Neg for &Measured {
+  type Output = Measured;
+  fn neg(self) -> Measured {
+    self.chain(-self.value, -1.0)
+  }
+}
+
+impl Neg for Measured {
+  type Output = Measured;
+  fn neg(self) -> Measured {
+    -&self
+  }
+}
+
+impl Add for &Measured {
+  type Output = Measured;
+  fn add(self, rhs: &Measured) -> Measured {
+    Measured::combine(self, rhs, self.value + rhs.value, 1.0, 1.0)
+  }
+}
+
+impl Sub for &Measured {
+  type Output = Measured;
+  fn sub(self, rhs: &Measured) -> Measured {
+    Measured::combine(self, rhs, self.value - rhs.value, 1.0, -1.0)
+  }
+}
+
+impl Mul for &Measured {
+  type Output = Measured;
+  fn mul(self, rhs: &Measured) -> Measured {
+    Measured::combine(self, rhs, self.value * rhs.value, rhs.value, self.value)
+  }
+}
+
+impl Div for &Measured {
+  type Output = Measured;
+  fn div(self, rhs: &Measured) -> Measured {
+    let q = self.value / rhs.value;
+    Measured::combine(self, rhs, q, 1.0 / rhs.value, -q / rhs.value)
+  }
+}
+
+impl Add<f64> for &Measured {
+  type Output = Measured;
+  fn add(self, rhs: f64) -> Measured {
+    self.chain(self.value + rhs, 1.0)
+  }
+}
+
+impl Sub<f64> for &Measured {
+  type Output = Measured;
+  fn sub(self, rhs: f64) -> Measured {
+    self.chain(self.value - rhs, 1.0)
+  }
+}
+
+impl Mul<f64> for &Measured {
+  type Output = Measured;
+  fn mul(self, rhs: f64) -> Measured {
+    self.chain(self.value * rhs, rhs)
+  }
+}
+
+impl Div<f64> for &Measured {
+  type Output = Measured;
+  fn div(self, rhs: f64) -> Measured {
+    self.chain(self.value / rhs, 1.0 / rhs)
+  }
+}
+
+impl Add<&Measured> for f64 {
+  type Output = Measured;
+  fn add(self, rhs: &Measured) -> Measured {
+    rhs + self
+  }
+}
+
+impl Sub<&Measured> for f64 {
+  type Output = Measured;
+  fn sub(self, rhs: &Measured) -> Measured {
+    rhs.chain(self - rhs.value, -1.0)
+  }
+}
+
+impl Mul<&Measured> for f64 {
+  type Output = Measured;
+  fn mul(self, rhs: &Measured) -> Measured {
+    rhs * self
+  }
+}
+
+impl Div<&Measured> for f64 {
+  type Output = Measured;
+  fn div(self, rhs: &Measured) -> Measured {
+    let q = self / rhs.value;
+    rhs.chain(q, -q / rhs.value)
+  }
+}
+
+// The operators above take references, so that a value can be reused without cloning it. These
+// forward the by-value forms to them.
+macro_rules! forward_by_value {
+  ($($trait:ident $method:ident),*) => {
+    $(
+      impl $trait for Measured {
+        type Output = Measured;
+        fn $method(self, rhs: Measured) -> Measured {
+          (&self).$method(&rhs)
+        }
+      }
+
+      impl $trait<&Measured> for Measured {
+        type Output = Measured;
+        fn $method(self, rhs: &Measured) -> Measured {
+          (&self).$method(rhs)
+        }
+      }
+
+      impl $trait<Measured> for &Measured {
+        type Output = Measured;
+        fn $method(self, rhs: Measured) -> Measured {
+          self.$method(&rhs)
+        }
+      }
+
+      impl $trait<f64> for Measured {
+        type Output = Measured;
+        fn $method(self, rhs: f64) -> Measured {
+          (&self).$method(rhs)
+        }
+      }
+
+      impl $trait<Measured> for f64 {
+        type Output = Measured;
+        fn $method(self, rhs: Measured) -> Measured {
+          self.$method(&rhs)
+        }
+      }
+    )*
+  };
+}
+
+forward_by_value!(Add add, Sub sub, Mul mul, Div div);
+
+// Formats as "value ± uncertainty" with the uncertainty rounded to two significant figures, or as
+// many as the precision asks for, and the value rounded to the same decimal place. The alternate
+// form {:#} gives the concise notation used by CODATA, with the uncertainty in units of the last
+// digit in parentheses: 6.67430(15)e-11 for 6.67430e-11 ± 0.00015e-11. Values far from 1 are
+// written with a common power of ten. Exact values are written in full, without the ± 0.
+impl fmt::Display for Measured {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let exponent_of = |formatted: String| -> i32 { formatted[formatted.find('e').unwrap() + 1..].parse().unwrap() };
+    let uncertainty = self.uncertainty();
+    if uncertainty == 0.0 && self.value.is_finite() {
+      // An exact value is written out in full, with no uncertainty, and in scientific notation on
+      // the same terms as below.
+      let exponent = if self.value == 0.0 { 0 } else { exponent_of(format!("{:e}", self.value)) };
+      return if (-5..6).contains(&exponent) { write!(f, "{}", self.value) } else { write!(f, "{:e}", self.value) };
+    }
+    if !uncertainty.is_finite() || !self.value.is_finite() {
+      return if f.alternate() {
+        write!(f, "{}", self.value)
+      } else {
+        write!(f, "{} ± {}", self.value, uncertainty)
+      };
+    }
+
+    let figures = f.precision().unwrap_or(2).max(1);
+    // Let the standard library do the rounding, so that an uncertainty like 0.0999 which rounds up
+    // to 0.10 gets the decimal exponent of the rounded value.
+    let uncertainty_exponent = exponent_of(format!("{:.*e}", figures - 1, uncertainty));
+    let last_digit = uncertainty_exponent - (figures as i32 - 1);
+    let unit = 10.0_f64.powi(last_digit);
+    let rounded_value = (self.value / unit).round() * unit;
+    let rounded_uncertainty = (uncertainty / unit).round() * unit;
+    let value_exponent = if rounded_value == 0.0 { uncertainty_exponent } else { exponent_of(format!("{rounded_value:e}")) };
+
+    // Scientific notation when the leading digit is too far from the decimal point.
+    let exponent = value_exponent.max(uncertainty_exponent);
+    let scientific = !(-5..6).contains(&exponent);
+    let shift = if scientific { exponent } else { 0 };
+    let decimals = (shift - last_digit).max(0) as usize;
+    let scale = 10.0_f64.powi(shift);
+    let value = rounded_value / scale;
+    let uncertainty = rounded_uncertainty / scale;
+    let suffix = if scientific { format!("e{exponent}") } else { String::new() };
+
+    if f.alternate() {
+      // In units of the last digit, unless that lies left of the decimal point.
+      let digits = if decimals > 0 { uncertainty * 10.0_f64.powi(decimals as i32) } else { uncertainty };
+      write!(f, "{value:.decimals$}({digits:.0}){suffix}")
+    } else if scientific {
+      write!(f, "({value:.decimals$} ± {uncertainty:.decimals$}){suffix}")
+    } else {
+      write!(f, "{value:.decimals$} ± {uncertainty:.decimals$}")
+    }
+  }
+}
+
+#[test]
+fn test_measured_propagation() {
+  use crate::utils::constants::{ATOMIC_UNIT_OF_LENGTH, BOHR_RADIUS, NEWTONIAN_CONSTANT_OF_GRAVITATION, SPEED_OF_LIGHT};
+
+  let x = Measured::new(2.0, 0.1);
+  let y = Measured::new(3.0, 0.2);
+
+  // Independent errors add in quadrature.
+  let sum = &x + &y;
+  assert_eq!(sum.value, 5.0);
+  assert!((sum.uncertainty() - 0.05_f64.sqrt()).abs() < 1.0e-16);
+  let product = &x * &y;
+  assert!((product.relative_uncertainty() - (0.05_f64.powi(2) + (0.2_f64 / 3.0).powi(2)).sqrt()).abs() < 1.0e-16);
+
+  // Correlated ones do not.
+  let zero = &x - &x;
+  assert_eq!((zero.value, zero.uncertainty()), (0.0, 0.0));
+  assert_eq!(zero, Measured::exact(0.0));
+  assert_eq!(&x * 0.0 + 1.0, Measured::exact(1.0));
+  assert!(((&x * &x).relative_uncertainty() - 2.0 * x.relative_uncertainty()).abs() < 1.0e-16);
+  assert!(((&x / &x).uncertainty()).abs() < 1.0e-17);
+  let independent = &x - &x.decorrelated();
+  assert!((independent.uncertainty() - 0.1 * 2.0_f64.sqrt()).abs() < 1.0e-16);
+
+  let u = &x + &y;
+  let v = &x - &y;
+  println!("cov(x+y, x-y) = {}", u.covariance(&v));
+  assert!((u.covariance(&v) - (0.01 - 0.04)).abs() < 1.0e-17);
+  assert!((u.correlation(&u) - 1.0).abs() < 1.0e-15);
+  assert_eq!(x.covariance(&y), 0.0);
+
+  // The elementary functions against their derivatives.
+  let cases = [
+    (x.exp(), 2.0_f64.exp()),
+    (x.ln(), 0.5),
+    (x.sqrt(), 0.5 / 2.0_f64.sqrt()),
+    (x.powi(3), 12.0),
+    (x.powf(1.5), 1.5 * 2.0_f64.sqrt()),
+    (x.recip(), -0.25),
+    (1.0 / &x, -0.25),
+    (x.sin(), 2.0_f64.cos()),
+    (x.cos(), -2.0_f64.sin()),
+    (x.tan(), 1.0 / 2.0_f64.cos().powi(2)),
+    (x.atan(), 0.2),
+    (x.sinh(), 2.0_f64.cosh()),
+    (x.cosh(), 2.0_f64.sinh()),
+    (x.tanh(), 1.0 / 2.0_f64.cosh().powi(2)),
+    (5.0 - &x * 3.0, -3.0),
+  ];
+  for (i, (result, derivative)) in cases.iter().enumerate() {
+    println!("{i}: {result}");
+    assert!((result.uncertainty() - 0.1 * derivative.abs()).abs() < 1.0e-15);
+  }
+
+  let estimate = Measured::from(Estimate::new(1.5, -1.0e-6));
+  assert_eq!((estimate.value, estimate.uncertainty()), (1.5, 1.0e-6));
+
+  // Using the same constant twice is one source of error, and exact constants carry none.
+  let g = Measured::from(NEWTONIAN_CONSTANT_OF_GRAVITATION);
+  let ratio = &g / Measured::from(NEWTONIAN_CONSTANT_OF_GRAVITATION);
+  assert_eq!((ratio.value, ratio.uncertainty()), (1.0, 0.0));
+  assert_eq!(Measured::from(SPEED_OF_LIGHT).uncertainty(), 0.0);
+
+  // Constants are told apart by name, so two which happen to share a value are independent, while
+  // another name for the same constant is not.
+  let lookalike = PhysicalConstant::measured("lookalike", g.value, g.uncertainty(), "");
+  assert_eq!(g.correlation(&Measured::from(lookalike)), 0.0);
+  assert_eq!(Measured::from(BOHR_RADIUS).correlation(&Measured::from(ATOMIC_UNIT_OF_LENGTH)), 1.0);
+}
+
+#[test]
+fn test_measured_display() {
+  use crate::utils::constants::{ELECTRON_MASS, NEWTONIAN_CONSTANT_OF_GRAVITATION, STEFAN_BOLTZMANN_CONSTANT};
+
+  let cases = [
+    (Measured::new(1.234_567, 0.001_234), "1.2346 ± 0.0012", "1.2346(12)"),
+    (Measured::new(1.234_567, 0.000_999_7), "1.2346 ± 0.0010", "1.2346(10)"),
+    (Measured::new(-42.0, 0.999), "-42.0 ± 1.0", "-42.0(10)"),
+    (Measured::new(12_345.6, 123.0), "12350 ± 120", "12350(120)"),
+    (Measured::new(0.000_5, 0.002), "0.0005 ± 0.0020", "0.0005(20)"),
+    (Measured::new(6.493_939_402, 1.75e-9), "6.4939394020 ± 0.0000000018", "6.4939394020(18)"),
+    (Measured::from(NEWTONIAN_CONSTANT_OF_GRAVITATION), "(6.67430 ± 0.00015)e-11", "6.67430(15)e-11"),
+    (Measured::from(ELECTRON_MASS), "(9.1093837015 ± 0.0000000028)e-31", "9.1093837015(28)e-31"),
+    (Measured::new(6.022e23, 3.0e20), "(6.0220 ± 0.0030)e23", "6.0220(30)e23"),
+    (Measured::from(STEFAN_BOLTZMANN_CONSTANT), "5.6703744191844294e-8", "5.6703744191844294e-8"),
+    (Measured::exact(-2.5), "-2.5", "-2.5"),
+    (Measured::exact(0.0), "0", "0"),
+  ];
+  for (measured, plain, concise) in cases {
+    println!("{measured} {measured:#}");
+    assert_eq!(format!("{measured}"), plain);
+    assert_eq!(format!("{measured:#}"), concise);
+  }
+  assert_eq!(format!("{:.1}", Measured::new(1.234_567, 0.001_234)), "1.235 ± 0.001");
+}
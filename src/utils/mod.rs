@@ -1 +1,14 @@
+pub mod big_uint;
+pub mod complex;
+pub mod constants;
+pub mod differentiate;
+pub mod dual;
+pub mod estimate;
 pub mod integrate;
+pub mod measured;
+pub mod plot;
+pub mod scalar;
+pub mod special;
+pub mod units;
+
+pub use estimate::Estimate;
@@ -0,0 +1,440 @@
+use std::error::Error;
+use std::ops::Range;
+
+use plotters::coord::Shift;
+use plotters::prelude::*;
+use textplots::{Chart, Plot, Shape};
+
+use crate::utils::plot::output::{self, Drawable, ImageFormat};
+
+// The categorical color cycle series are drawn in unless given a color of their own. The first four
+// are the blue, orange, green and magenta the exercises have always used; the rest follow the
+// familiar tab10 palette.
+pub const COLOR_CYCLE: [RGBColor; 10] = [
+  RGBColor(31, 119, 180),
+  RGBColor(255, 127, 14),
+  RGBColor(44, 160, 44),
+  RGBColor(160, 32, 160),
+  RGBColor(214, 39, 40),
+  RGBColor(140, 86, 75),
+  RGBColor(227, 119, 194),
+  RGBColor(127, 127, 127),
+  RGBColor(188, 189, 34),
+  RGBColor(23, 190, 207),
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LegendPosition {
+  UpperLeft,
+  UpperRight,
+  LowerLeft,
+  LowerRight,
+}
+
+enum Data<'a> {
+  // Sampled across the x range once it is known.
+  Function(Box<dyn Fn(f64) -> f64 + 'a>),
+  Points(Vec<(f64, f64)>),
+}
+
+// One curve or set of points on a Figure. An empty label leaves it out of the legend.
+pub struct Series<'a> {
+  label: String,
+  data: Data<'a>,
+  color: Option<RGBColor>,
+  line_width: Option<u32>,
+  markers: bool,
+}
+
+impl<'a> Series<'a> {
+  pub fn function<F: Fn(f64) -> f64 + 'a>(label: &str, f: F) -> Series<'a> {
+    Series::new(label, Data::Function(Box::new(f)))
+  }
+
+  pub fn points<I: IntoIterator<Item = (f64, f64)>>(label: &str, points: I) -> Series<'a> {
+    Series::new(label, Data::Points(points.into_iter().collect()))
+  }
+
+  fn new(label: &str, data: Data<'a>) -> Series<'a> {
+    Series { label: label.to_string(), data, color: None, line_width: None, markers: false }
+  }
+
+  pub fn color(mut self, color: RGBColor) -> Series<'a> {
+    self.color = Some(color);
+    self
+  }
+
+  pub fn line_width(mut self, width: u32) -> Series<'a> {
+    self.line_width = Some(width);
+    self
+  }
+
+  // Draws a marker at each point rather than joining them with a line.
+  pub fn markers(mut self) -> Series<'a> {
+    self.markers = true;
+    self
+  }
+}
+
+struct Layout {
+  x_range: Range<f64>,
+  y_range: Range<f64>,
+  series: Vec<Vec<(f64, f64)>>,
+}
+
+pub(crate) struct Axis {
+  pub(crate) label: String,
+  pub(crate) unit: String,
+  // Tick labels show x / scale, so that an axis in meters can be labelled in micrometers.
+  pub(crate) scale: f64,
+  pub(crate) range: Option<Range<f64>>,
+}
+
+impl Axis {
+  pub(crate) fn new() -> Axis {
+    Axis { label: String::new(), unit: String::new(), scale: 1.0, range: None }
+  }
+
+  pub(crate) fn description(&self) -> String {
+    match (self.label.is_empty(), self.unit.is_empty()) {
+      (_, true) => self.label.clone(),
+      (true, false) => self.unit.clone(),
+      (false, false) => format!("{} ({})", self.label, self.unit),
+    }
+  }
+}
+
+// A line plot built up a piece at a time and rendered in one call:
+//
+//   let path = output_path("bessel")?;
+//   Figure::new("Bessel Functions J_m(x)")
+//     .x_range(0.0, 20.0)
+//     .function("J_1(x)", |x| bessel_jn(1, x))
+//     .function("J_2(x)", |x| bessel_jn(2, x))
+//     .save(&path)?;
+//
+// Ranges left unset are fitted to the data, and series without a color of their own take the next
+// one from COLOR_CYCLE.
+pub struct Figure<'a> {
+  title: String,
+  size: (u32, u32),
+  x: Axis,
+  y: Axis,
+  samples: usize,
+  line_width: u32,
+  legend: LegendPosition,
+  series: Vec<Series<'a>>,
+}
+
+impl<'a> Figure<'a> {
+  pub fn new(title: &str) -> Figure<'a> {
+    Figure {
+      title: title.to_string(),
+      size: (800, 600),
+      x: Axis::new(),
+      y: Axis::new(),
+      samples: 200,
+      line_width: 2,
+      legend: LegendPosition::UpperRight,
+      series: Vec::new(),
+    }
+  }
+
+  pub fn size(mut self, width: u32, height: u32) -> Figure<'a> {
+    self.size = (width, height);
+    self
+  }
+
+  pub fn x_range(mut self, min: f64, max: f64) -> Figure<'a> {
+    self.x.range = Some(min..max);
+    self
+  }
+
+  pub fn y_range(mut self, min: f64, max: f64) -> Figure<'a> {
+    self.y.range = Some(min..max);
+    self
+  }
+
+  pub fn x_label(mut self, label: &str) -> Figure<'a> {
+    self.x.label = label.to_string();
+    self
+  }
+
+  pub fn y_label(mut self, label: &str) -> Figure<'a> {
+    self.y.label = label.to_string();
+    self
+  }
+
+  // The unit shown after the axis label. Coordinates are still given in the base unit and the
+  // tick labels are divided by scale, which is 1 unless the unit is a multiple of it.
+  pub fn x_unit(mut self, unit: &str, scale: f64) -> Figure<'a> {
+    self.x.unit = unit.to_string();
+    self.x.scale = scale;
+    self
+  }
+
+  pub fn y_unit(mut self, unit: &str, scale: f64) -> Figure<'a> {
+    self.y.unit = unit.to_string();
+    self.y.scale = scale;
+    self
+  }
+
+  // How many points functions are sampled at.
+  pub fn samples(mut self, samples: usize) -> Figure<'a> {
+    self.samples = samples.max(2);
+    self
+  }
+
+  // The default width of the lines, in pixels.
+  pub fn line_width(mut self, width: u32) -> Figure<'a> {
+    self.line_width = width;
+    self
+  }
+
+  pub fn legend(mut self, position: LegendPosition) -> Figure<'a> {
+    self.legend = position;
+    self
+  }
+
+  pub fn series(mut self, series: Series<'a>) -> Figure<'a> {
+    self.series.push(series);
+    self
+  }
+
+  pub fn function<F: Fn(f64) -> f64 + 'a>(self, label: &str, f: F) -> Figure<'a> {
+    self.series(Series::function(label, f))
+  }
+
+  pub fn points<I: IntoIterator<Item = (f64, f64)>>(self, label: &str, points: I) -> Figure<'a> {
+    self.series(Series::points(label, points))
+  }
+
+  // Writes the figure as a PNG or SVG image, depending on the extension of the path.
+  pub fn save(&self, path: &str) -> Result<(), Box<dyn Error>> {
+    output::save(self, path)
+  }
+
+  pub fn save_png(&self, path: &str) -> Result<(), Box<dyn Error>> {
+    output::save_as(self, path, ImageFormat::Png)
+  }
+
+  pub fn save_svg(&self, path: &str) -> Result<(), Box<dyn Error>> {
+    output::save_as(self, path, ImageFormat::Svg)
+  }
+
+  // The figure drawn in braille characters, width dots across and height dots down (two and four to
+  // a character). It shows exactly the data and ranges the images do, so that the quick look in the
+  // terminal and the saved image always agree. The terminal has no room for the title, axis labels
+  // or legend, and every series is drawn the same.
+  pub fn to_terminal(&self, width: u32, height: u32) -> Result<String, Box<dyn Error>> {
+    let layout = self.layout()?;
+    let (x_range, y_range) = (&layout.x_range, &layout.y_range);
+    let in_range = |&(x, y): &(f64, f64)| x_range.contains(&x) && y >= y_range.start && y <= y_range.end;
+
+    let points: Vec<Vec<(f32, f32)>> = layout.series.iter()
+      .map(|points| points.iter().filter(|p| in_range(p)).map(|&(x, y)| (x as f32, y as f32)).collect())
+      .collect();
+    let shapes: Vec<Shape> = self.series.iter().zip(&points)
+      .map(|(series, points)| if series.markers { Shape::Points(points) } else { Shape::Lines(points) })
+      .collect();
+
+    let mut chart = Chart::new_with_y_range(
+      width, height,
+      x_range.start as f32, x_range.end as f32,
+      y_range.start as f32, y_range.end as f32,
+    );
+    let mut chart_ref = &mut chart;
+    for shape in &shapes {
+      chart_ref = chart_ref.lineplot(shape);
+    }
+    chart_ref.axis();
+    chart_ref.figures();
+    Ok(format!("{chart_ref}"))
+  }
+
+  // Prints the figure to the terminal at the size the exercises have always used.
+  pub fn print_terminal(&self) -> Result<(), Box<dyn Error>> {
+    println!("{}", self.to_terminal(75, 30)?);
+    Ok(())
+  }
+
+  // The x range to plot over: as given, or spanning the sampled series.
+  fn resolved_x_range(&self) -> Result<Range<f64>, Box<dyn Error>> {
+    if let Some(range) = &self.x.range {
+      return Ok(range.clone());
+    }
+    let xs = self.series.iter().flat_map(|series| match &series.data {
+      Data::Points(points) => points.iter().map(|p| p.0).collect(),
+      Data::Function(_) => Vec::new(),
+    });
+    fitted_range(xs, 0.0).ok_or_else(|| "a Figure with only functions needs an x range".into())
+  }
+
+  // Every series as points, with functions sampled evenly across the x range.
+  fn sampled_series(&self, x_range: &Range<f64>) -> Vec<Vec<(f64, f64)>> {
+    self.series.iter().map(|series| match &series.data {
+      Data::Points(points) => points.clone(),
+      Data::Function(f) => (0..self.samples).map(|i| {
+        let fraction = i as f64 / (self.samples - 1) as f64;
+        let x = x_range.start * (1.0 - fraction) + x_range.end * fraction;
+        (x, f(x))
+      }).collect(),
+    }).collect()
+  }
+
+  // The ranges and data every rendering works from, with non-finite points dropped.
+  fn layout(&self) -> Result<Layout, Box<dyn Error>> {
+    let x_range = self.resolved_x_range()?;
+    let series: Vec<Vec<(f64, f64)>> = self.sampled_series(&x_range).into_iter()
+      .map(|points| points.into_iter().filter(|p| p.0.is_finite() && p.1.is_finite()).collect())
+      .collect();
+    let y_range = match &self.y.range {
+      Some(range) => range.clone(),
+      None => fitted_range(series.iter().flatten().map(|p| p.1), 0.05).unwrap_or(-1.0..1.0),
+    };
+    Ok(Layout { x_range, y_range, series })
+  }
+}
+
+impl Drawable for Figure<'_> {
+  fn image_size(&self) -> (u32, u32) {
+    self.size
+  }
+
+  fn draw<DB: DrawingBackend>(&self, root: &DrawingArea<DB, Shift>) -> Result<(), Box<dyn Error>>
+  where
+    DB::ErrorType: 'static,
+  {
+    root.fill(&WHITE)?;
+
+    let Layout { x_range, y_range, series: sampled } = self.layout()?;
+
+    let (x_scale, y_scale) = (self.x.scale, self.y.scale);
+    let mut chart = ChartBuilder::on(root)
+      .caption(&self.title, ("sans-serif", 40).into_font())
+      .margin(10)
+      .x_label_area_size(if self.x.description().is_empty() { 30 } else { 50 })
+      .y_label_area_size(if self.y.description().is_empty() { 60 } else { 80 })
+      .build_cartesian_2d(x_range, y_range)?;
+
+    chart.configure_mesh()
+      .x_desc(self.x.description())
+      .y_desc(self.y.description())
+      .axis_desc_style(("sans-serif", 16))
+      .x_label_formatter(&|x| format_tick(x / x_scale))
+      .y_label_formatter(&|y| format_tick(y / y_scale))
+      .draw()?;
+
+    for (i, (series, points)) in self.series.iter().zip(sampled).enumerate() {
+      let color = series.color.unwrap_or(COLOR_CYCLE[i % COLOR_CYCLE.len()]);
+      let style = color.stroke_width(series.line_width.unwrap_or(self.line_width));
+      let annotation = if series.markers {
+        chart.draw_series(points.into_iter().map(|p| Circle::new(p, 3, style.filled())))?
+      } else {
+        chart.draw_series(LineSeries::new(points, style))?
+      };
+      if !series.label.is_empty() {
+        annotation
+          .label(series.label.as_str())
+          .legend(move |(x, y)| PathElement::new([(x, y), (x + 20, y)], style));
+      }
+    }
+
+    if self.series.iter().any(|series| !series.label.is_empty()) {
+      let position = match self.legend {
+        LegendPosition::UpperLeft => SeriesLabelPosition::UpperLeft,
+        LegendPosition::UpperRight => SeriesLabelPosition::UpperRight,
+        LegendPosition::LowerLeft => SeriesLabelPosition::LowerLeft,
+        LegendPosition::LowerRight => SeriesLabelPosition::LowerRight,
+      };
+      chart.configure_series_labels()
+        .label_font(("sans-serif", 20).into_font())
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .position(position)
+        .draw()?;
+    }
+
+    root.present()?;
+    Ok(())
+  }
+}
+
+// The smallest range holding all the finite values, widened by the given fraction of its width on
+// each side, or None if there are no finite values. A single value gets a range of width 2.
+fn fitted_range<I: IntoIterator<Item = f64>>(values: I, padding: f64) -> Option<Range<f64>> {
+  let (min, max) = values.into_iter()
+    .filter(|v| v.is_finite())
+    .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), v| (min.min(v), max.max(v)));
+  if min > max {
+    return None;
+  }
+  if min == max {
+    return Some(min - 1.0..max + 1.0);
+  }
+  let pad = padding * (max - min);
+  Some(min - pad..max + pad)
+}
+
+// Tick labels with just enough digits, so 0.30000000000000004 reads as 0.3.
+pub(crate) fn format_tick(v: f64) -> String {
+  let rounded = format!("{v:.6e}").parse::<f64>().unwrap_or(v);
+  if rounded != 0.0 && (rounded.abs() >= 1.0e5 || rounded.abs() < 1.0e-3) {
+    format!("{rounded:e}")
+  } else {
+    format!("{rounded}")
+  }
+}
+
+#[test]
+fn test_figure() {
+  assert_eq!(fitted_range([3.0, -1.0, f64::NAN, 1.0], 0.0), Some(-1.0..3.0));
+  assert_eq!(fitted_range([2.0, 0.0], 0.25), Some(-0.5..2.5));
+  assert_eq!(fitted_range([5.0], 0.1), Some(4.0..6.0));
+  assert_eq!(fitted_range([f64::INFINITY], 0.1), None);
+
+  assert_eq!(format_tick(0.1 + 0.2), "0.3");
+  assert_eq!(format_tick(-2.5e-7), "-2.5e-7");
+  assert_eq!(format_tick(350.0), "350");
+
+  let figure = Figure::new("Test").function("sin", f64::sin);
+  assert!(figure.resolved_x_range().is_err());
+  let figure = figure.points("data", [(1.0, 0.5), (4.0, 0.25)]).samples(4);
+  assert_eq!(figure.resolved_x_range().unwrap(), 1.0..4.0);
+  let sampled = figure.sampled_series(&(1.0..4.0));
+  assert_eq!(sampled[0].iter().map(|p| p.0).collect::<Vec<_>>(), vec![1.0, 2.0, 3.0, 4.0]);
+  assert_eq!(sampled[1], vec![(1.0, 0.5), (4.0, 0.25)]);
+
+  // Every rendering works from the same layout, with the non-finite points dropped.
+  let layout = Figure::new("Test").x_range(0.0, 2.0).points("", [(0.0, 1.0), (1.0, f64::NAN), (2.0, 3.0)]).layout().unwrap();
+  assert_eq!(layout.series[0], vec![(0.0, 1.0), (2.0, 3.0)]);
+  assert_eq!(layout.y_range, 0.9..3.1);
+
+  // The terminal view is labelled with the same ranges as the images.
+  let terminal = figure.to_terminal(40, 16).unwrap();
+  println!("{terminal}");
+  let lines: Vec<&str> = terminal.trim_end().lines().collect();
+  let (x_axis, rows) = lines.split_last().unwrap();
+  assert!(x_axis.starts_with("1.0") && x_axis.ends_with("4.0"));
+  assert!(rows[0].ends_with(" 1.0") && rows[rows.len() - 1].ends_with(" -0.8"));
+  assert!(rows.iter().any(|line| line.chars().any(|c| c != '\u{2800}' && ('\u{2800}'..='\u{28ff}').contains(&c))));
+
+  assert!(figure.save("test.jpg").is_err());
+  let mut svg = String::new();
+  {
+    let root = SVGBackend::with_string(&mut svg, (320, 240)).into_drawing_area();
+    figure.draw(&root).unwrap();
+  }
+  assert!(svg.contains("<svg") && svg.contains("sin"));
+
+  // Render into memory, which exercises the whole chart without touching the file system.
+  let (width, height) = (320, 240);
+  let mut buffer = vec![0_u8; (width * height * 3) as usize];
+  {
+    let root = BitMapBackend::with_buffer(&mut buffer, (width, height)).into_drawing_area();
+    figure.x_label("x").y_unit("m", 1.0e-6).draw(&root).unwrap();
+  }
+  // Something was drawn in the first color of the cycle.
+  let RGBColor(r, g, b) = COLOR_CYCLE[0];
+  assert!(buffer.chunks(3).any(|pixel| pixel == [r, g, b]));
+}
@@ -0,0 +1,470 @@
+use std::error::Error;
+use std::ops::Range;
+
+use ndarray::Array2;
+use plotters::coord::Shift;
+use plotters::prelude::*;
+
+use crate::utils::plot::figure::{format_tick, Axis};
+use crate::utils::plot::output::{self, Drawable, ImageFormat};
+
+// Colormaps for values in [0, 1]. Viridis and magma are perceptually uniform, so equal steps in value
+// look like equal steps in color; the diverging map runs from blue through gray to red and is meant
+// for values either side of zero.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Colormap {
+  Viridis,
+  Magma,
+  Grayscale,
+  Diverging,
+}
+
+// Samples of each map at evenly spaced values, taken from matplotlib's viridis, magma and coolwarm.
+const VIRIDIS: [RGBColor; 9] = [
+  RGBColor(68, 1, 84),
+  RGBColor(71, 44, 122),
+  RGBColor(59, 82, 139),
+  RGBColor(44, 114, 142),
+  RGBColor(33, 145, 140),
+  RGBColor(40, 174, 128),
+  RGBColor(94, 201, 98),
+  RGBColor(173, 220, 48),
+  RGBColor(253, 231, 37),
+];
+
+const MAGMA: [RGBColor; 9] = [
+  RGBColor(0, 0, 4),
+  RGBColor(28, 16, 68),
+  RGBColor(79, 18, 123),
+  RGBColor(129, 37, 129),
+  RGBColor(181, 54, 122),
+  RGBColor(229, 80, 100),
+  RGBColor(251, 135, 97),
+  RGBColor(254, 194, 135),
+  RGBColor(252, 253, 191),
+];
+
+const GRAYSCALE: [RGBColor; 2] = [RGBColor(0, 0, 0), RGBColor(255, 255, 255)];
+
+const DIVERGING: [RGBColor; 9] = [
+  RGBColor(59, 76, 192),
+  RGBColor(98, 130, 234),
+  RGBColor(141, 176, 254),
+  RGBColor(184, 208, 249),
+  RGBColor(221, 221, 221),
+  RGBColor(245, 196, 173),
+  RGBColor(244, 154, 123),
+  RGBColor(222, 96, 77),
+  RGBColor(180, 4, 38),
+];
+
+impl Colormap {
+  // The color for t, which is clamped to [0, 1], interpolating linearly between the samples.
+  pub fn color(&self, t: f64) -> RGBColor {
+    let samples: &[RGBColor] = match self {
+      Colormap::Viridis => &VIRIDIS,
+      Colormap::Magma => &MAGMA,
+      Colormap::Grayscale => &GRAYSCALE,
+      Colormap::Diverging => &DIVERGING,
+    };
+    let position = t.clamp(0.0, 1.0) * (samples.len() - 1) as f64;
+    let i = (position as usize).min(samples.len() - 2);
+    let fraction = position - i as f64;
+    let mix = |a: u8, b: u8| (a as f64 * (1.0 - fraction) + b as f64 * fraction).round() as u8;
+    let (RGBColor(r0, g0, b0), RGBColor(r1, g1, b1)) = (samples[i], samples[i + 1]);
+    RGBColor(mix(r0, r1), mix(g0, g1), mix(b0, b1))
+  }
+}
+
+// How values are mapped onto the colormap. Log spreads out values spanning many orders of magnitude,
+// and Power(gamma) with gamma < 1 brings up faint detail next to a bright peak.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Normalization {
+  Linear,
+  Log,
+  Power(f64),
+}
+
+impl Normalization {
+  // Where value falls in range, from 0 at the start to 1 at the end, with values outside the range
+  // clamped to it. NaN has no place and gives None.
+  pub fn normalize(&self, value: f64, range: &Range<f64>) -> Option<f64> {
+    if value.is_nan() {
+      return None;
+    }
+    let t = match self {
+      Normalization::Log if value <= 0.0 => 0.0,
+      Normalization::Log => (value / range.start).ln() / (range.end / range.start).ln(),
+      Normalization::Linear | Normalization::Power(_) => (value - range.start) / (range.end - range.start),
+    };
+    let t = t.clamp(0.0, 1.0);
+    match self {
+      Normalization::Power(gamma) => Some(t.powf(*gamma)),
+      _ => Some(t),
+    }
+  }
+
+  // The value that normalizes to t, for labelling the colorbar.
+  pub fn value_at(&self, t: f64, range: &Range<f64>) -> f64 {
+    match self {
+      Normalization::Linear => range.start + t * (range.end - range.start),
+      Normalization::Log => range.start * (range.end / range.start).powf(t),
+      Normalization::Power(gamma) => range.start + t.powf(1.0 / gamma) * (range.end - range.start),
+    }
+  }
+}
+
+// A density plot of values on a regular grid, drawn with a colorbar:
+//
+//   let path = output_path("diffraction")?;
+//   Heatmap::new("Diffraction Pattern", intensities)
+//     .x_extent(-1.0e-6, 1.0e-6)
+//     .y_extent(-1.0e-6, 1.0e-6)
+//     .normalization(Normalization::Power(0.5))
+//     .save(&path)?;
+//
+// data[[i, j]] fills the cell i along x and j along y, with the cells splitting the extents evenly.
+// The value range is fitted to the data unless given, symmetrically about zero for the diverging
+// colormap, and takes only the positive values into account on a log scale.
+pub struct Heatmap {
+  title: String,
+  size: (u32, u32),
+  data: Array2<f64>,
+  x: Axis,
+  y: Axis,
+  colormap: Colormap,
+  normalization: Normalization,
+  value_range: Option<Range<f64>>,
+  value_label: String,
+}
+
+impl Heatmap {
+  pub fn new(title: &str, data: Array2<f64>) -> Heatmap {
+    let (nx, ny) = data.dim();
+    let mut x = Axis::new();
+    let mut y = Axis::new();
+    x.range = Some(0.0..nx as f64);
+    y.range = Some(0.0..ny as f64);
+    Heatmap {
+      title: title.to_string(),
+      size: (800, 600),
+      data,
+      x,
+      y,
+      colormap: Colormap::Viridis,
+      normalization: Normalization::Linear,
+      value_range: None,
+      value_label: String::new(),
+    }
+  }
+
+  pub fn size(mut self, width: u32, height: u32) -> Heatmap {
+    self.size = (width, height);
+    self
+  }
+
+  // The coordinates the grid spans, which default to the cell indices.
+  pub fn x_extent(mut self, min: f64, max: f64) -> Heatmap {
+    self.x.range = Some(min..max);
+    self
+  }
+
+  pub fn y_extent(mut self, min: f64, max: f64) -> Heatmap {
+    self.y.range = Some(min..max);
+    self
+  }
+
+  pub fn x_label(mut self, label: &str) -> Heatmap {
+    self.x.label = label.to_string();
+    self
+  }
+
+  pub fn y_label(mut self, label: &str) -> Heatmap {
+    self.y.label = label.to_string();
+    self
+  }
+
+  // As for Figure, coordinates stay in the base unit and the tick labels are divided by scale.
+  pub fn x_unit(mut self, unit: &str, scale: f64) -> Heatmap {
+    self.x.unit = unit.to_string();
+    self.x.scale = scale;
+    self
+  }
+
+  pub fn y_unit(mut self, unit: &str, scale: f64) -> Heatmap {
+    self.y.unit = unit.to_string();
+    self.y.scale = scale;
+    self
+  }
+
+  pub fn colormap(mut self, colormap: Colormap) -> Heatmap {
+    self.colormap = colormap;
+    self
+  }
+
+  pub fn normalization(mut self, normalization: Normalization) -> Heatmap {
+    self.normalization = normalization;
+    self
+  }
+
+  // The values at the two ends of the colormap. Values beyond them take the end colors.
+  pub fn value_range(mut self, min: f64, max: f64) -> Heatmap {
+    self.value_range = Some(min..max);
+    self
+  }
+
+  // The label along the colorbar.
+  pub fn value_label(mut self, label: &str) -> Heatmap {
+    self.value_label = label.to_string();
+    self
+  }
+
+  // Writes the heatmap as a PNG or SVG image, depending on the extension of the path.
+  pub fn save(&self, path: &str) -> Result<(), Box<dyn Error>> {
+    output::save(self, path)
+  }
+
+  pub fn save_png(&self, path: &str) -> Result<(), Box<dyn Error>> {
+    output::save_as(self, path, ImageFormat::Png)
+  }
+
+  pub fn save_svg(&self, path: &str) -> Result<(), Box<dyn Error>> {
+    output::save_as(self, path, ImageFormat::Svg)
+  }
+
+  // The heatmap in the terminal as width by height characters colored with 24-bit ANSI escapes,
+  // each character showing two cells one above the other. A last line shows the colorbar.
+  pub fn to_terminal(&self, width: u32, height: u32) -> Result<String, Box<dyn Error>> {
+    let (x_range, y_range) = self.extents();
+    let value_range = self.resolved_value_range()?;
+    let (nx, ny) = self.data.dim();
+    let fg = |RGBColor(r, g, b): RGBColor| format!("\x1b[38;2;{r};{g};{b}m");
+    let bg = |RGBColor(r, g, b): RGBColor| format!("\x1b[48;2;{r};{g};{b}m");
+
+    // The color of the cell under a point given as fractions across and up the grid.
+    let color_at = |across: f64, up: f64| {
+      let i = ((across * nx as f64) as usize).min(nx.saturating_sub(1));
+      let j = ((up * ny as f64) as usize).min(ny.saturating_sub(1));
+      let value = *self.data.get((i, j))?;
+      self.normalization.normalize(value, &value_range).map(|t| self.colormap.color(t))
+    };
+
+    let mut lines = Vec::new();
+    for row in 0..height {
+      let mut line = String::new();
+      for column in 0..width {
+        let across = (column as f64 + 0.5) / width as f64;
+        let upper = color_at(across, 1.0 - (2 * row) as f64 / (2 * height) as f64 - 0.25 / height as f64);
+        let lower = color_at(across, 1.0 - (2 * row + 1) as f64 / (2 * height) as f64 - 0.25 / height as f64);
+        match (upper, lower) {
+          (Some(upper), Some(lower)) => line += &format!("{}{}\u{2580}", fg(upper), bg(lower)),
+          (Some(upper), None) => line += &format!("\x1b[49m{}\u{2580}", fg(upper)),
+          (None, Some(lower)) => line += &format!("\x1b[49m{}\u{2584}", fg(lower)),
+          (None, None) => line += "\x1b[0m ",
+        }
+      }
+      line += "\x1b[0m";
+      if row == 0 {
+        line += &format!(" {}", format_tick(y_range.end / self.y.scale));
+      } else if row == height - 1 {
+        line += &format!(" {}", format_tick(y_range.start / self.y.scale));
+      }
+      lines.push(line);
+    }
+
+    let (x_min, x_max) = (format_tick(x_range.start / self.x.scale), format_tick(x_range.end / self.x.scale));
+    let gap = (width as usize).saturating_sub(x_min.len() + x_max.len()).max(1);
+    lines.push(format!("{x_min}{}{x_max}", " ".repeat(gap)));
+
+    let colorbar: String = (0..width)
+      .map(|column| fg(self.colormap.color((column as f64 + 0.5) / width as f64)) + "\u{2588}")
+      .collect();
+    lines.push(format!(
+      "{colorbar}\x1b[0m {} to {}",
+      format_tick(self.normalization.value_at(0.0, &value_range)),
+      format_tick(self.normalization.value_at(1.0, &value_range)),
+    ));
+    Ok(lines.join("\n"))
+  }
+
+  pub fn print_terminal(&self) -> Result<(), Box<dyn Error>> {
+    println!("{}", self.to_terminal(75, 30)?);
+    Ok(())
+  }
+
+  fn extents(&self) -> (Range<f64>, Range<f64>) {
+    let extent = |axis: &Axis| axis.range.clone().unwrap_or(0.0..1.0);
+    (extent(&self.x), extent(&self.y))
+  }
+
+  // The values at the ends of the colormap: as given, or spanning the data that can be shown. A
+  // range or exponent the normalization can't work with would turn every cell to NaN, and so black,
+  // and is an error instead.
+  fn resolved_value_range(&self) -> Result<Range<f64>, Box<dyn Error>> {
+    if let Normalization::Power(gamma) = self.normalization {
+      if !(gamma > 0.0 && gamma.is_finite()) {
+        return Err(format!("a power normalization needs a positive exponent, not {gamma}").into());
+      }
+    }
+    if let Some(range) = &self.value_range {
+      if range.start >= range.end || range.start.is_nan() || range.end.is_nan() {
+        return Err(format!("the value range {}..{} is empty", range.start, range.end).into());
+      }
+      if self.normalization == Normalization::Log && range.start <= 0.0 {
+        return Err(format!("a log normalization needs a value range above zero, not {}..{}", range.start, range.end).into());
+      }
+      return Ok(range.clone());
+    }
+    let log = self.normalization == Normalization::Log;
+    let (min, max) = self.data.iter()
+      .filter(|v| v.is_finite() && (!log || **v > 0.0))
+      .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &v| (min.min(v), max.max(v)));
+    Ok(match (min < max, log) {
+      (false, true) if min == max => min / 10.0..max * 10.0,
+      (false, true) => 1.0..10.0,
+      (false, false) if min == max => min - 1.0..max + 1.0,
+      (false, false) => 0.0..1.0,
+      (true, _) if self.colormap == Colormap::Diverging && !log => {
+        let bound = min.abs().max(max.abs());
+        -bound..bound
+      }
+      (true, _) => min..max,
+    })
+  }
+}
+
+impl Drawable for Heatmap {
+  fn image_size(&self) -> (u32, u32) {
+    self.size
+  }
+
+  fn draw<DB: DrawingBackend>(&self, root: &DrawingArea<DB, Shift>) -> Result<(), Box<dyn Error>>
+  where
+    DB::ErrorType: 'static,
+  {
+    root.fill(&WHITE)?;
+
+    let (x_range, y_range) = self.extents();
+    let value_range = self.resolved_value_range()?;
+    let (x_scale, y_scale) = (self.x.scale, self.y.scale);
+    let x_label_area = if self.x.description().is_empty() { 30 } else { 50 };
+
+    let area = root.titled(&self.title, ("sans-serif", 40))?;
+    let colorbar_width = if self.value_label.is_empty() { 100 } else { 120 };
+    let (plot_area, colorbar_area) = area.split_horizontally(area.dim_in_pixel().0 as i32 - colorbar_width);
+
+    let mut chart = ChartBuilder::on(&plot_area)
+      .margin(10)
+      .x_label_area_size(x_label_area)
+      .y_label_area_size(if self.y.description().is_empty() { 60 } else { 80 })
+      .build_cartesian_2d(x_range.clone(), y_range.clone())?;
+
+    chart.configure_mesh()
+      .disable_mesh()
+      .x_desc(self.x.description())
+      .y_desc(self.y.description())
+      .axis_desc_style(("sans-serif", 16))
+      .x_label_formatter(&|x| format_tick(x / x_scale))
+      .y_label_formatter(&|y| format_tick(y / y_scale))
+      .draw()?;
+
+    let (nx, ny) = self.data.dim();
+    let dx = (x_range.end - x_range.start) / nx as f64;
+    let dy = (y_range.end - y_range.start) / ny as f64;
+    chart.draw_series(self.data.indexed_iter().filter_map(|((i, j), &value)| {
+      let t = self.normalization.normalize(value, &value_range)?;
+      let (x, y) = (x_range.start + i as f64 * dx, y_range.start + j as f64 * dy);
+      Some(Rectangle::new([(x, y), (x + dx, y + dy)], self.colormap.color(t).filled()))
+    }))?;
+
+    // The colorbar runs over normalized values, so its ticks are labelled with the values they stand
+    // for and fall evenly in the normalized scale.
+    let mut colorbar = ChartBuilder::on(&colorbar_area)
+      .margin(10)
+      .x_label_area_size(x_label_area)
+      .right_y_label_area_size(colorbar_width - 40)
+      .build_cartesian_2d(0.0..1.0, 0.0..1.0)?;
+
+    colorbar.configure_mesh()
+      .disable_mesh()
+      .disable_x_axis()
+      .y_desc(self.value_label.as_str())
+      .axis_desc_style(("sans-serif", 16))
+      // The default size follows the width of the area, which is far too small for the narrow bar.
+      .y_label_style(("sans-serif", 12))
+      .y_label_formatter(&|t| format_tick(self.normalization.value_at(*t, &value_range)))
+      .draw()?;
+
+    let steps = 256;
+    colorbar.draw_series((0..steps).map(|step| {
+      let t = step as f64 / steps as f64;
+      let color = self.colormap.color(t + 0.5 / steps as f64);
+      Rectangle::new([(0.0, t), (1.0, t + 1.0 / steps as f64)], color.filled())
+    }))?;
+
+    root.present()?;
+    Ok(())
+  }
+}
+
+#[test]
+fn test_heatmap() {
+  assert_eq!(Colormap::Grayscale.color(0.0), RGBColor(0, 0, 0));
+  assert_eq!(Colormap::Grayscale.color(0.5), RGBColor(128, 128, 128));
+  assert_eq!(Colormap::Grayscale.color(2.0), RGBColor(255, 255, 255));
+  assert_eq!(Colormap::Viridis.color(0.0), VIRIDIS[0]);
+  assert_eq!(Colormap::Magma.color(1.0), MAGMA[8]);
+  assert_eq!(Colormap::Diverging.color(0.5), RGBColor(221, 221, 221));
+
+  let range = 1.0..100.0;
+  assert_eq!(Normalization::Linear.normalize(50.5, &range), Some(0.5));
+  assert_eq!(Normalization::Linear.normalize(-3.0, &range), Some(0.0));
+  assert!((Normalization::Log.normalize(10.0, &range).unwrap() - 0.5).abs() < 1.0e-15);
+  assert_eq!(Normalization::Log.normalize(-1.0, &range), Some(0.0));
+  assert_eq!(Normalization::Power(0.5).normalize(25.75, &(1.0..100.0)), Some(0.5));
+  assert_eq!(Normalization::Linear.normalize(f64::NAN, &range), None);
+  for normalization in [Normalization::Linear, Normalization::Log, Normalization::Power(0.5)] {
+    for value in [1.0, 7.0, 42.0, 100.0] {
+      let t = normalization.normalize(value, &range).unwrap();
+      assert!((normalization.value_at(t, &range) - value).abs() < 1.0e-12 * value);
+    }
+  }
+
+  let data = Array2::from_shape_fn((4, 3), |(i, j)| i as f64 - j as f64);
+  let heatmap = Heatmap::new("Test", data.clone());
+  assert_eq!(heatmap.resolved_value_range().unwrap(), -2.0..3.0);
+  assert_eq!(heatmap.extents(), (0.0..4.0, 0.0..3.0));
+  let heatmap = heatmap.colormap(Colormap::Diverging);
+  assert_eq!(heatmap.resolved_value_range().unwrap(), -3.0..3.0);
+  let heatmap = heatmap.normalization(Normalization::Log);
+  assert_eq!(heatmap.resolved_value_range().unwrap(), 1.0..3.0);
+
+  // Settings which would leave every cell NaN are refused, whichever order they are given in.
+  assert!(heatmap.to_terminal(4, 3).is_ok());
+  let heatmap = heatmap.value_range(0.0, 3.0);
+  assert!(heatmap.to_terminal(4, 3).is_err());
+  let heatmap = heatmap.normalization(Normalization::Power(0.0));
+  assert!(heatmap.to_terminal(4, 3).is_err());
+  let heatmap = heatmap.normalization(Normalization::Power(0.5));
+  assert!(heatmap.to_terminal(4, 3).is_ok());
+  assert!(heatmap.value_range(3.0, 3.0).normalization(Normalization::Linear).to_terminal(4, 3).is_err());
+
+  // The terminal shows the top row of cells first, with the y extent beside it.
+  let heatmap = Heatmap::new("Test", data).colormap(Colormap::Grayscale).x_extent(0.0, 2.0);
+  let terminal = heatmap.to_terminal(4, 3).unwrap();
+  println!("{terminal}");
+  let lines: Vec<&str> = terminal.lines().collect();
+  assert_eq!(lines.len(), 5);
+  assert!(lines[0].starts_with("\x1b[38;2;0;0;0m\x1b[48;2;0;0;0m\u{2580}") && lines[0].ends_with(" 3"));
+  assert!(lines[2].starts_with("\x1b[38;2;102;102;102m\x1b[48;2;102;102;102m\u{2580}\x1b[38;2;153;153;153m"));
+  assert!(lines[2].ends_with("\x1b[0m 0"));
+  assert!(lines[3].starts_with('0') && lines[3].ends_with('2'));
+  assert!(lines[4].ends_with(" -2 to 3"));
+
+  let mut svg = String::new();
+  {
+    let root = SVGBackend::with_string(&mut svg, (320, 240)).into_drawing_area();
+    heatmap.value_label("value").draw(&root).unwrap();
+  }
+  assert!(svg.contains("<svg") && svg.contains("value") && svg.contains("#CCCCCC"));
+}
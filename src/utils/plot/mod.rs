@@ -0,0 +1,13 @@
+pub mod figure;
+pub mod heatmap;
+pub mod output;
+
+pub use figure::Figure;
+pub use figure::LegendPosition;
+pub use figure::Series;
+pub use figure::COLOR_CYCLE;
+pub use heatmap::Colormap;
+pub use heatmap::Heatmap;
+pub use heatmap::Normalization;
+pub use output::output_path;
+pub use output::ImageFormat;
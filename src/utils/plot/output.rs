@@ -0,0 +1,159 @@
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use plotters::coord::Shift;
+use plotters::prelude::*;
+
+// Where the exercises save their figures. They go to out/ as PNGs unless the command line says
+// otherwise,
+//
+//   cargo run --bin ch05_exercise03 -- --out-dir figures --format svg
+//
+// or, failing that, the NEWMAN_OUT_DIR and NEWMAN_PLOT_FORMAT environment variables do. SVGs stay
+// sharp at any size, so they are the ones to put in a report, though a heatmap of many cells makes
+// for a large file.
+pub const DEFAULT_OUT_DIR: &str = "out";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImageFormat {
+  Png,
+  Svg,
+}
+
+impl ImageFormat {
+  pub fn extension(&self) -> &'static str {
+    match self {
+      ImageFormat::Png => "png",
+      ImageFormat::Svg => "svg",
+    }
+  }
+
+  // The format named by the extension of path.
+  fn of_path(path: &str) -> Result<ImageFormat, Box<dyn Error>> {
+    match Path::new(path).extension().and_then(|extension| extension.to_str()) {
+      Some("png") => Ok(ImageFormat::Png),
+      Some("svg") => Ok(ImageFormat::Svg),
+      _ => Err(format!("cannot tell the image format of {path}, which should end in .png or .svg").into()),
+    }
+  }
+
+  fn parse(name: &str) -> Result<ImageFormat, Box<dyn Error>> {
+    match name.to_ascii_lowercase().as_str() {
+      "png" => Ok(ImageFormat::Png),
+      "svg" => Ok(ImageFormat::Svg),
+      _ => Err(format!("unknown image format {name}, which should be png or svg").into()),
+    }
+  }
+}
+
+// The path to save the figure called name to, in the output directory and with the extension of
+// the chosen format, ready to pass to Figure::save or Heatmap::save. The directory is created if
+// need be.
+pub fn output_path(name: &str) -> Result<String, Box<dyn Error>> {
+  let (dir, format) = output_policy(
+    std::env::args().skip(1),
+    std::env::var("NEWMAN_OUT_DIR").ok(),
+    std::env::var("NEWMAN_PLOT_FORMAT").ok(),
+  )?;
+  fs::create_dir_all(&dir)?;
+  let path = dir.join(format!("{name}.{}", format.extension()));
+  Ok(path.to_string_lossy().into_owned())
+}
+
+// What Figure and Heatmap have in common for saving: an image size, and a way to draw themselves on
+// any plotters backend. Their save, save_png and save_svg all come down to save_as.
+pub(crate) trait Drawable {
+  fn image_size(&self) -> (u32, u32);
+
+  fn draw<DB: DrawingBackend>(&self, root: &DrawingArea<DB, Shift>) -> Result<(), Box<dyn Error>>
+  where
+    DB::ErrorType: 'static;
+}
+
+// Saves in the format given by the extension of the path.
+pub(crate) fn save<D: Drawable>(drawable: &D, path: &str) -> Result<(), Box<dyn Error>> {
+  save_as(drawable, path, ImageFormat::of_path(path)?)
+}
+
+pub(crate) fn save_as<D: Drawable>(drawable: &D, path: &str, format: ImageFormat) -> Result<(), Box<dyn Error>> {
+  match format {
+    ImageFormat::Png => drawable.draw(&BitMapBackend::new(path, drawable.image_size()).into_drawing_area()),
+    ImageFormat::Svg => drawable.draw(&SVGBackend::new(path, drawable.image_size()).into_drawing_area()),
+  }
+}
+
+// The output directory and format from the command line arguments, which take precedence, and the
+// environment variables, with empty variables treated as unset.
+fn output_policy<I: IntoIterator<Item = String>>(
+  args: I,
+  dir_var: Option<String>,
+  format_var: Option<String>,
+) -> Result<(PathBuf, ImageFormat), Box<dyn Error>> {
+  let mut dir = PathBuf::from(dir_var.filter(|dir| !dir.is_empty()).unwrap_or(DEFAULT_OUT_DIR.to_string()));
+  let mut format = match format_var.filter(|format| !format.is_empty()) {
+    Some(format) => ImageFormat::parse(&format)?,
+    None => ImageFormat::Png,
+  };
+
+  // Both --flag value and --flag=value are accepted.
+  let mut args = args.into_iter();
+  while let Some(arg) = args.next() {
+    let (flag, value) = match arg.split_once('=') {
+      Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+      None => (arg.clone(), None),
+    };
+    if flag != "--out-dir" && flag != "--format" {
+      return Err(format!("unknown argument {arg}, expected --out-dir DIR or --format png|svg").into());
+    }
+    let value = match value {
+      Some(value) => value,
+      None => args.next().ok_or_else(|| format!("{flag} needs a value"))?,
+    };
+    if flag == "--out-dir" {
+      dir = PathBuf::from(value);
+    } else {
+      format = ImageFormat::parse(&value)?;
+    }
+  }
+
+  Ok((dir, format))
+}
+
+#[test]
+fn test_output_policy() {
+  let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+
+  assert_eq!(output_policy(args(&[]), None, None).unwrap(), (PathBuf::from("out"), ImageFormat::Png));
+  assert_eq!(
+    output_policy(args(&[]), Some("figures".to_string()), Some("SVG".to_string())).unwrap(),
+    (PathBuf::from("figures"), ImageFormat::Svg),
+  );
+  assert_eq!(
+    output_policy(args(&[]), Some(String::new()), Some(String::new())).unwrap(),
+    (PathBuf::from("out"), ImageFormat::Png),
+  );
+
+  // The command line wins over the environment.
+  assert_eq!(
+    output_policy(args(&["--out-dir", "report", "--format=svg"]), Some("figures".to_string()), Some("png".to_string())).unwrap(),
+    (PathBuf::from("report"), ImageFormat::Svg),
+  );
+  assert_eq!(
+    output_policy(args(&["--out-dir=a/b"]), None, Some("svg".to_string())).unwrap(),
+    (PathBuf::from("a/b"), ImageFormat::Svg),
+  );
+
+  assert!(output_policy(args(&["--format", "pdf"]), None, None).is_err());
+  assert!(output_policy(args(&["--out-dir"]), None, None).is_err());
+  assert!(output_policy(args(&["--verbose"]), None, None).is_err());
+  assert!(output_policy(args(&[]), None, Some("jpg".to_string())).is_err());
+}
+
+#[test]
+fn test_image_format_of_path() {
+  assert_eq!(ImageFormat::of_path("out/figure.png").unwrap(), ImageFormat::Png);
+  assert_eq!(ImageFormat::of_path("figure.svg").unwrap(), ImageFormat::Svg);
+  assert!(ImageFormat::of_path("figure.jpg").is_err());
+  assert!(ImageFormat::of_path("figure").is_err());
+}
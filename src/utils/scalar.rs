@@ -0,0 +1,48 @@
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+// The arithmetic and elementary functions shared by f64 and the number types which extend it,
+// such as dual numbers for automatic differentiation. Writing a function generically over Scalar
+// instead of f64 lets it be evaluated with any of them.
+pub trait Scalar:
+  Copy
+  + From<f64>
+  + Neg<Output = Self>
+  + Add<Output = Self>
+  + Sub<Output = Self>
+  + Mul<Output = Self>
+  + Div<Output = Self>
+  + Add<f64, Output = Self>
+  + Sub<f64, Output = Self>
+  + Mul<f64, Output = Self>
+  + Div<f64, Output = Self>
+{
+  fn exp(self) -> Self;
+  fn ln(self) -> Self;
+  fn sqrt(self) -> Self;
+  fn powi(self, n: i32) -> Self;
+  fn powf(self, p: f64) -> Self;
+  fn recip(self) -> Self;
+  fn sin(self) -> Self;
+  fn cos(self) -> Self;
+  fn tan(self) -> Self;
+  fn atan(self) -> Self;
+  fn sinh(self) -> Self;
+  fn cosh(self) -> Self;
+  fn tanh(self) -> Self;
+}
+
+impl Scalar for f64 {
+  fn exp(self) -> f64 { f64::exp(self) }
+  fn ln(self) -> f64 { f64::ln(self) }
+  fn sqrt(self) -> f64 { f64::sqrt(self) }
+  fn powi(self, n: i32) -> f64 { f64::powi(self, n) }
+  fn powf(self, p: f64) -> f64 { f64::powf(self, p) }
+  fn recip(self) -> f64 { f64::recip(self) }
+  fn sin(self) -> f64 { f64::sin(self) }
+  fn cos(self) -> f64 { f64::cos(self) }
+  fn tan(self) -> f64 { f64::tan(self) }
+  fn atan(self) -> f64 { f64::atan(self) }
+  fn sinh(self) -> f64 { f64::sinh(self) }
+  fn cosh(self) -> f64 { f64::cosh(self) }
+  fn tanh(self) -> f64 { f64::tanh(self) }
+}
@@ -0,0 +1,262 @@
+use std::f64::consts::PI;
+
+use crate::utils::special::bessel::{bessel_ik_scaled, bessel_jy};
+#[cfg(test)]
+use crate::utils::special::assert_close;
+
+const EPSILON: f64 = 1e-16;
+const MAXIMUM_ITERATIONS: usize = 100;
+
+// Ai(0) = 1 / (3^(2/3) Gamma(2/3)) and -Ai'(0) = 1 / (3^(1/3) Gamma(1/3)).
+const AIRY_C1: f64 = 0.355_028_053_887_817_2;
+const AIRY_C2: f64 = 0.258_819_403_792_806_8;
+
+const FRAC_1_SQRT_3: f64 = 0.577_350_269_189_625_8;
+
+// Within this the Maclaurin series converges quickly without cancellation, and beyond it the
+// functions come from Bessel functions of order 1/3 and 2/3.
+const SERIES_LIMIT: f64 = 1.0;
+
+// The Airy functions solve y'' = x y. Ai decays for x > 0 and Bi grows, and for x < 0 both oscillate
+// with slowly shrinking amplitude, which makes them the model solutions near a classical turning
+// point of a linear potential.
+
+// Ai(x), Ai'(x), Bi(x) and Bi'(x) from the Maclaurin series
+//   Ai = c1 f - c2 g, Bi = sqrt(3) (c1 f + c2 g),
+//   f = sum_k 3^k (1/3)_k x^(3k) / (3k)!, g = sum_k 3^k (2/3)_k x^(3k+1) / (3k+1)!.
+// Abramowitz and Stegun 10.4.2 through 10.4.5.
+fn airy_series(x: f64) -> (f64, f64, f64, f64) {
+  let x3 = x * x * x;
+  let (mut f_term, mut g_term) = (1.0, x);
+  let (mut f, mut g) = (1.0, x);
+  let (mut fp, mut gp) = (0.0, 1.0);
+  for k in 1..MAXIMUM_ITERATIONS {
+    let k = k as f64;
+    fp += f_term * x * x / (3.0 * k - 1.0);
+    gp += g_term * x * x / (3.0 * k);
+    f_term *= x3 / ((3.0 * k - 1.0) * (3.0 * k));
+    g_term *= x3 / ((3.0 * k) * (3.0 * k + 1.0));
+    f += f_term;
+    g += g_term;
+    if f_term.abs() < EPSILON * f.abs() && g_term.abs() < EPSILON * g.abs().max(f_term.abs()) {
+      break;
+    }
+  }
+  let sqrt_3 = 1.0 / FRAC_1_SQRT_3;
+  (
+    AIRY_C1 * f - AIRY_C2 * g,
+    AIRY_C1 * fp - AIRY_C2 * gp,
+    sqrt_3 * (AIRY_C1 * f + AIRY_C2 * g),
+    sqrt_3 * (AIRY_C1 * fp + AIRY_C2 * gp),
+  )
+}
+
+// The Airy functions and their derivatives (Ai(x), Ai'(x), Bi(x), Bi'(x)). With z = 2/3 |x|^(3/2),
+//   x > 0: Ai = sqrt(x/3) K_1/3(z) / pi, Bi = sqrt(x) (2/sqrt(3) I_1/3(z) + K_1/3(z) / pi),
+//   x < 0: Ai = sqrt(|x|)/2 (J_1/3(z) - Y_1/3(z) / sqrt(3)), Bi = -sqrt(|x|)/2 (Y_1/3(z) + J_1/3(z) / sqrt(3)),
+// and similarly for the derivatives with order 2/3. See Numerical Recipes section 6.7 and
+// Abramowitz and Stegun 10.4.14 through 10.4.17.
+pub fn airy(x: f64) -> (f64, f64, f64, f64) {
+  if x.abs() <= SERIES_LIMIT {
+    return airy_series(x);
+  }
+
+  let ax = x.abs();
+  let root = ax.sqrt();
+  let z = 2.0 / 3.0 * ax * root;
+  if x > 0.0 {
+    // The scaled functions keep Ai from underflowing and Bi from overflowing before they must.
+    let (decay, growth) = ((-z).exp(), z.exp());
+    let (i, k, _, _) = bessel_ik_scaled(1.0 / 3.0, z);
+    let ai = root * FRAC_1_SQRT_3 * k * decay / PI;
+    let bi = root * (k * decay / PI + 2.0 * FRAC_1_SQRT_3 * i * growth);
+    let (i, k, _, _) = bessel_ik_scaled(2.0 / 3.0, z);
+    let aip = -x * FRAC_1_SQRT_3 * k * decay / PI;
+    let bip = x * (k * decay / PI + 2.0 * FRAC_1_SQRT_3 * i * growth);
+    (ai, aip, bi, bip)
+  } else {
+    let (j, y, _, _) = bessel_jy(1.0 / 3.0, z);
+    let ai = 0.5 * root * (j - FRAC_1_SQRT_3 * y);
+    let bi = -0.5 * root * (y + FRAC_1_SQRT_3 * j);
+    let (j, y, _, _) = bessel_jy(2.0 / 3.0, z);
+    let aip = 0.5 * ax * (FRAC_1_SQRT_3 * y + j);
+    let bip = 0.5 * ax * (FRAC_1_SQRT_3 * j - y);
+    (ai, aip, bi, bip)
+  }
+}
+
+pub fn airy_ai(x: f64) -> f64 {
+  airy(x).0
+}
+
+pub fn airy_ai_prime(x: f64) -> f64 {
+  airy(x).1
+}
+
+pub fn airy_bi(x: f64) -> f64 {
+  airy(x).2
+}
+
+pub fn airy_bi_prime(x: f64) -> f64 {
+  airy(x).3
+}
+
+// The asymptotic forms of the zeros, Abramowitz and Stegun 10.4.105 and 10.4.106,
+//   T(t) ~ t^(2/3) (1 + 5/48 t^-2 - 5/36 t^-4 + 77125/82944 t^-6 - 108056875/6967296 t^-8),
+//   U(t) ~ t^(2/3) (1 - 7/48 t^-2 + 35/288 t^-4 - 181223/207360 t^-6 + 18683371/1244160 t^-8).
+const AIRY_ZERO_T_COEFFICIENTS: [f64; 4] = [5.0 / 48.0, -5.0 / 36.0, 77_125.0 / 82_944.0, -108_056_875.0 / 6_967_296.0];
+const AIRY_ZERO_U_COEFFICIENTS: [f64; 4] =
+  [-7.0 / 48.0, 35.0 / 288.0, -181_223.0 / 207_360.0, 18_683_371.0 / 1_244_160.0];
+
+// The series is summed only while its terms shrink, which for the first zeros is after a term or
+// two, close enough for Newton's method to finish.
+fn airy_zero_asymptotic(t: f64, coefficients: &[f64]) -> f64 {
+  let s = 1.0 / (t * t);
+  let mut power = 1.0;
+  let mut sum = 1.0;
+  let mut previous = f64::INFINITY;
+  for c in coefficients {
+    power *= s;
+    let term = c * power;
+    if term.abs() >= previous {
+      break;
+    }
+    sum += term;
+    previous = term.abs();
+  }
+  t.powf(2.0 / 3.0) * sum
+}
+
+// Newton's method from the asymptotic guess. Since y'' = x y, the step for a zero of y' only needs
+// y itself, and the iteration stops once the step no longer shrinks.
+fn airy_zero_refine(guess: f64, derivative: bool, select: impl Fn((f64, f64, f64, f64)) -> (f64, f64)) -> f64 {
+  let mut x = guess;
+  let mut previous_step = f64::INFINITY;
+  for _ in 0..MAXIMUM_ITERATIONS {
+    let (y, yp) = select(airy(x));
+    let step = if derivative { yp / (x * y) } else { y / yp };
+    x -= step;
+    if step.abs() >= previous_step.abs() || step.abs() <= EPSILON * x.abs() {
+      break;
+    }
+    previous_step = step;
+  }
+  x
+}
+
+// The kth zero of Ai, k >= 1, all of which lie on the negative axis: a_1 = -2.338..., a_2 = -4.088....
+pub fn airy_ai_zero(k: usize) -> f64 {
+  assert!(k >= 1, "the zeros are numbered from 1");
+  let guess = -airy_zero_asymptotic(3.0 * PI / 8.0 * (4 * k - 1) as f64, &AIRY_ZERO_T_COEFFICIENTS);
+  airy_zero_refine(guess, false, |(ai, aip, _, _)| (ai, aip))
+}
+
+// The kth zero of Ai', k >= 1: a'_1 = -1.018....
+pub fn airy_ai_prime_zero(k: usize) -> f64 {
+  assert!(k >= 1, "the zeros are numbered from 1");
+  let guess = -airy_zero_asymptotic(3.0 * PI / 8.0 * (4 * k - 3) as f64, &AIRY_ZERO_U_COEFFICIENTS);
+  airy_zero_refine(guess, true, |(ai, aip, _, _)| (ai, aip))
+}
+
+// The kth zero of Bi on the negative axis, k >= 1: b_1 = -1.173....
+pub fn airy_bi_zero(k: usize) -> f64 {
+  assert!(k >= 1, "the zeros are numbered from 1");
+  let guess = -airy_zero_asymptotic(3.0 * PI / 8.0 * (4 * k - 3) as f64, &AIRY_ZERO_T_COEFFICIENTS);
+  airy_zero_refine(guess, false, |(_, _, bi, bip)| (bi, bip))
+}
+
+// The kth zero of Bi' on the negative axis, k >= 1: b'_1 = -2.294....
+pub fn airy_bi_prime_zero(k: usize) -> f64 {
+  assert!(k >= 1, "the zeros are numbered from 1");
+  let guess = -airy_zero_asymptotic(3.0 * PI / 8.0 * (4 * k - 1) as f64, &AIRY_ZERO_U_COEFFICIENTS);
+  airy_zero_refine(guess, true, |(_, _, bi, bip)| (bi, bip))
+}
+
+#[test]
+fn test_airy_reference_values() {
+  // Reference values computed to 30 digits with mpmath.
+  let cases = [
+    // x, Ai, Ai', Bi, Bi'
+    (-10.0, 0.040_241_238_486_443_19, 0.996_265_044_132_79, -0.314_679_829_643_838_6, 0.119_414_113_399_909_23),
+    (-3.7, -0.282_013_061_841_931_4, -0.582_727_803_652_958_2, 0.292_352_610_071_452_1, -0.524_613_614_909_683_3),
+    (-1.2, 0.526_194_374_802_120_1, 0.107_031_569_272_280_76, -0.015_821_370_184_632_057, 0.601_710_157_437_464_4),
+    (-0.5, 0.475_728_091_610_539_6, -0.204_081_670_339_547_38, 0.380_352_659_751_053_87, 0.505_933_713_623_847_2),
+    (0.0, 0.355_028_053_887_817_2, -0.258_819_403_792_806_8, 0.614_926_627_446_000_7, 0.448_288_357_353_826_4),
+    (0.5, 0.231_693_606_480_833_48, -0.224_910_532_664_683_9, 0.854_277_043_103_155_4, 0.544_572_564_140_592_3),
+    (1.5, 0.071_749_497_008_105_41, -0.097_382_012_842_301_32, 1.878_941_503_747_895, 1.886_212_254_848_165_5),
+    (4.0, 0.000_951_563_851_204_801_8, -0.001_958_640_950_204_179, 83.847_071_408_468_14, 161.926_683_504_613_4),
+    (12.0, 1.393_184_688_875_360_7e-13, -4.854_736_554_985_309e-13, 329_807_225_829.074_16, 1_135_507_502_443.370_8),
+  ];
+  for (x, ai, aip, bi, bip) in cases {
+    println!("x={x} Ai={} Ai'={} Bi={} Bi'={}", airy_ai(x), airy_ai_prime(x), airy_bi(x), airy_bi_prime(x));
+    if x < -1.0 {
+      // Where the functions oscillate the error is measured against their envelopes,
+      // |x|^(-1/4) / sqrt(pi) for Ai and Bi and |x|^(1/4) / sqrt(pi) for the derivatives.
+      let envelope = (-x).powf(-0.25) / PI.sqrt();
+      let envelope_prime = (-x).powf(0.25) / PI.sqrt();
+      assert!((airy_ai(x) - ai).abs() < 1.0e-14 * envelope);
+      assert!((airy_ai_prime(x) - aip).abs() < 1.0e-14 * envelope_prime);
+      assert!((airy_bi(x) - bi).abs() < 1.0e-14 * envelope);
+      assert!((airy_bi_prime(x) - bip).abs() < 1.0e-14 * envelope_prime);
+    } else {
+      assert_close(airy_ai(x), ai, 4.0e-15);
+      assert_close(airy_ai_prime(x), aip, 4.0e-15);
+      assert_close(airy_bi(x), bi, 4.0e-15);
+      assert_close(airy_bi_prime(x), bip, 4.0e-15);
+    }
+  }
+
+  // Ai decays like e^(-2/3 x^(3/2)), and only underflows when it must.
+  assert_close(airy_ai(100.0), 2.634_482_152_088_184_6e-291, 2.0e-13);
+  assert_eq!(airy_ai(120.0), 0.0);
+  assert_eq!(airy_bi(120.0), f64::INFINITY);
+}
+
+#[test]
+fn test_airy_identities() {
+  use crate::utils::integrate::integrate_gaussian_quadrature_adaptive;
+
+  // The Wronskian Ai Bi' - Ai' Bi = 1 / pi, across the series and both Bessel regions.
+  for k in 0..=100 {
+    let x = -20.0 + 0.3 * k as f64;
+    let (ai, aip, bi, bip) = airy(x);
+    let wronskian = ai * bip - aip * bi;
+    let scale = (ai * bip).abs().max((aip * bi).abs());
+    assert!((wronskian - 1.0 / PI).abs() < 2.0e-15 * scale.max(1.0), "x={x} W={wronskian}");
+  }
+
+  // Ai'' = x Ai, by a central difference.
+  for x in [-4.2, -0.9, 0.3, 2.6] {
+    let h = 1.0e-3;
+    let second = (airy_ai(x + h) - 2.0 * airy_ai(x) + airy_ai(x - h)) / (h * h);
+    assert!((second - x * airy_ai(x)).abs() < 1.0e-6, "x={x}");
+  }
+
+  // int_0^inf Ai(x) dx = 1/3, with the tail past x = 20 below 1e-27.
+  let integral = integrate_gaussian_quadrature_adaptive(0.0, 20.0, 1.0e-15, airy_ai);
+  assert!((integral - 1.0 / 3.0).abs() < 1.0e-14, "integral={integral}");
+}
+
+#[test]
+fn test_airy_zeros() {
+  // Reference values computed to 30 digits with mpmath.
+  let cases = [
+    // k, a_k, a'_k, b_k, b'_k
+    (1, -2.338_107_410_459_767, -1.018_792_971_647_471, -1.173_713_222_709_128, -2.294_439_682_614_123),
+    (2, -4.087_949_444_130_97, -3.248_197_582_179_836_6, -3.271_093_302_836_353, -4.073_155_089_071_828),
+    (3, -5.520_559_828_095_551, -4.820_099_211_178_736, -4.830_737_841_662_016, -5.512_395_729_663_599_5),
+    (100, -60.455_557_274_116_7, -60.253_295_964_424_794, -60.253_364_825_808_37, -60.455_488_872_571_41),
+  ];
+  for (k, a, ap, b, bp) in cases {
+    println!("k={k} a={} a'={} b={} b'={}", airy_ai_zero(k), airy_ai_prime_zero(k), airy_bi_zero(k), airy_bi_prime_zero(k));
+    assert_close(airy_ai_zero(k), a, 4.0e-16);
+    assert_close(airy_ai_prime_zero(k), ap, 4.0e-16);
+    assert_close(airy_bi_zero(k), b, 4.0e-16);
+    assert_close(airy_bi_prime_zero(k), bp, 4.0e-16);
+  }
+
+  // The zeros of Ai and Ai' interlace.
+  for k in 1..40 {
+    assert!(airy_ai_zero(k + 1) < airy_ai_prime_zero(k + 1) && airy_ai_prime_zero(k + 1) < airy_ai_zero(k));
+  }
+}
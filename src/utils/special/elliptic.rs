@@ -0,0 +1,538 @@
+use std::f64::consts::{FRAC_PI_2, PI};
+
+#[cfg(test)]
+use crate::utils::special::assert_close;
+
+const EPSILON: f64 = 1e-16;
+const MAXIMUM_ITERATIONS: usize = 100;
+
+// The duplication steps of the Carlson integrals stop once the arguments agree to this relative
+// tolerance, when the truncated Taylor series that finishes them is accurate to about 1e-17.
+// See Numerical Recipes section 6.12.
+const CARLSON_TOLERANCE: f64 = 0.0015;
+
+// Descending Landen transformations for the Jacobi elliptic functions converge quadratically, so
+// they stop once the moduli agree to the square root of the working precision.
+const LANDEN_TOLERANCE: f64 = 1e-8;
+
+// The arithmetic-geometric mean of a and b, the common limit of a_(n+1) = (a_n + b_n) / 2 and
+// b_(n+1) = sqrt(a_n b_n), which converges quadratically. Abramowitz and Stegun 17.6.
+pub fn agm(a: f64, b: f64) -> f64 {
+  assert!(a >= 0.0 && b >= 0.0, "the arithmetic-geometric mean needs a, b >= 0");
+  let (mut a, mut b) = (a, b);
+  for _ in 0..MAXIMUM_ITERATIONS {
+    if (a - b).abs() <= EPSILON * a {
+      break;
+    }
+    (a, b) = (0.5 * (a + b), (a * b).sqrt());
+  }
+  a
+}
+
+// Carlson's symmetric integral of the first kind
+//   R_F(x, y, z) = 1/2 int_0^inf dt / sqrt((t+x) (t+y) (t+z)),
+// for x, y, z >= 0 with at most one of them zero. DLMF 19.16.1 and 19.36.1.
+pub fn carlson_rf(x: f64, y: f64, z: f64) -> f64 {
+  assert!(x.min(y).min(z) >= 0.0, "R_F needs x, y, z >= 0");
+  let (mut x, mut y, mut z) = (x, y, z);
+  let (mut dx, mut dy, mut dz, mut average);
+  loop {
+    let (sx, sy, sz) = (x.sqrt(), y.sqrt(), z.sqrt());
+    let lambda = sx * (sy + sz) + sy * sz;
+    x = 0.25 * (x + lambda);
+    y = 0.25 * (y + lambda);
+    z = 0.25 * (z + lambda);
+    average = (x + y + z) / 3.0;
+    dx = (average - x) / average;
+    dy = (average - y) / average;
+    dz = (average - z) / average;
+    if dx.abs().max(dy.abs()).max(dz.abs()) <= CARLSON_TOLERANCE {
+      break;
+    }
+  }
+  let e2 = dx * dy - dz * dz;
+  let e3 = dx * dy * dz;
+  (1.0 + (e2 / 24.0 - 0.1 - 3.0 / 44.0 * e3) * e2 + e3 / 14.0) / average.sqrt()
+}
+
+// Carlson's symmetric integral of the second kind
+//   R_D(x, y, z) = 3/2 int_0^inf dt / (sqrt((t+x) (t+y)) (t+z)^(3/2)),
+// for x, y >= 0 with at most one of them zero, and z > 0. DLMF 19.16.5.
+pub fn carlson_rd(x: f64, y: f64, z: f64) -> f64 {
+  assert!(x.min(y) >= 0.0 && z > 0.0, "R_D needs x, y >= 0 and z > 0");
+  let (mut x, mut y, mut z) = (x, y, z);
+  let (mut dx, mut dy, mut dz, mut average);
+  let mut sum = 0.0;
+  let mut factor = 1.0;
+  loop {
+    let (sx, sy, sz) = (x.sqrt(), y.sqrt(), z.sqrt());
+    let lambda = sx * (sy + sz) + sy * sz;
+    sum += factor / (sz * (z + lambda));
+    factor *= 0.25;
+    x = 0.25 * (x + lambda);
+    y = 0.25 * (y + lambda);
+    z = 0.25 * (z + lambda);
+    average = 0.2 * (x + y + 3.0 * z);
+    dx = (average - x) / average;
+    dy = (average - y) / average;
+    dz = (average - z) / average;
+    if dx.abs().max(dy.abs()).max(dz.abs()) <= CARLSON_TOLERANCE {
+      break;
+    }
+  }
+  let (c1, c2, c3, c4) = (3.0 / 14.0, 1.0 / 6.0, 9.0 / 22.0, 3.0 / 26.0);
+  let (c5, c6) = (0.25 * c3, 1.5 * c4);
+  let ea = dx * dy;
+  let eb = dz * dz;
+  let ec = ea - eb;
+  let ed = ea - 6.0 * eb;
+  let ee = ed + ec + ec;
+  let series = 1.0 + ed * (-c1 + c5 * ed - c6 * dz * ee) + dz * (c2 * ee + dz * (-c3 * ec + dz * c4 * ea));
+  3.0 * sum + factor * series / (average * average.sqrt())
+}
+
+// Carlson's degenerate integral R_C(x, y) = R_F(x, y, y), which is elementary. For y < 0 it gives
+// the Cauchy principal value. DLMF 19.16.6 and 19.20.5.
+pub fn carlson_rc(x: f64, y: f64) -> f64 {
+  assert!(x >= 0.0 && y != 0.0, "R_C needs x >= 0 and y != 0");
+  let (mut x, mut y, weight) = if y > 0.0 { (x, y, 1.0) } else { (x - y, -y, (x / (x - y)).sqrt()) };
+  let (mut s, mut average);
+  loop {
+    let lambda = 2.0 * x.sqrt() * y.sqrt() + y;
+    x = 0.25 * (x + lambda);
+    y = 0.25 * (y + lambda);
+    average = (x + y + y) / 3.0;
+    s = (y - average) / average;
+    if s.abs() <= CARLSON_TOLERANCE {
+      break;
+    }
+  }
+  weight * (1.0 + s * s * (0.3 + s * (1.0 / 7.0 + s * (0.375 + s * 9.0 / 22.0)))) / average.sqrt()
+}
+
+// Carlson's symmetric integral of the third kind
+//   R_J(x, y, z, p) = 3/2 int_0^inf dt / (sqrt((t+x) (t+y) (t+z)) (t+p)),
+// for x, y, z >= 0 with at most one of them zero, and p != 0. For p < 0 it gives the Cauchy
+// principal value, by way of DLMF 19.20.14 which relates it to a positive p.
+pub fn carlson_rj(x: f64, y: f64, z: f64, p: f64) -> f64 {
+  assert!(x.min(y).min(z) >= 0.0 && p != 0.0, "R_J needs x, y, z >= 0 and p != 0");
+  if p < 0.0 {
+    let lowest = x.min(y).min(z);
+    let highest = x.max(y).max(z);
+    let middle = x + y + z - lowest - highest;
+    let a = 1.0 / (middle - p);
+    let b = a * (highest - middle) * (middle - lowest);
+    let q = middle + b;
+    let rho = lowest * highest / middle;
+    let tau = p * q / middle;
+    let rj = carlson_rj_positive(lowest, middle, highest, q);
+    return a * (b * rj + 3.0 * (carlson_rc(rho, tau) - carlson_rf(lowest, middle, highest)));
+  }
+  carlson_rj_positive(x, y, z, p)
+}
+
+fn carlson_rj_positive(x: f64, y: f64, z: f64, p: f64) -> f64 {
+  let (mut x, mut y, mut z, mut p) = (x, y, z, p);
+  let (mut dx, mut dy, mut dz, mut dp, mut average);
+  let mut sum = 0.0;
+  let mut factor = 1.0;
+  loop {
+    let (sx, sy, sz) = (x.sqrt(), y.sqrt(), z.sqrt());
+    let lambda = sx * (sy + sz) + sy * sz;
+    let alpha = (p * (sx + sy + sz) + sx * sy * sz).powi(2);
+    let beta = p * (p + lambda).powi(2);
+    sum += factor * carlson_rc(alpha, beta);
+    factor *= 0.25;
+    x = 0.25 * (x + lambda);
+    y = 0.25 * (y + lambda);
+    z = 0.25 * (z + lambda);
+    p = 0.25 * (p + lambda);
+    average = 0.2 * (x + y + z + p + p);
+    dx = (average - x) / average;
+    dy = (average - y) / average;
+    dz = (average - z) / average;
+    dp = (average - p) / average;
+    if dx.abs().max(dy.abs()).max(dz.abs()).max(dp.abs()) <= CARLSON_TOLERANCE {
+      break;
+    }
+  }
+  let (c1, c2, c3, c4) = (3.0 / 14.0, 1.0 / 3.0, 3.0 / 22.0, 3.0 / 26.0);
+  let (c5, c6, c7, c8) = (0.75 * c3, 1.5 * c4, 0.5 * c2, c3 + c3);
+  let ea = dx * (dy + dz) + dy * dz;
+  let eb = dx * dy * dz;
+  let ec = dp * dp;
+  let ed = ea - 3.0 * ec;
+  let ee = eb + 2.0 * dp * (ea - ec);
+  let series = 1.0 + ed * (-c1 + c5 * ed - c6 * ee) + eb * (c7 + dp * (-c8 + dp * c4)) + dp * ea * (c2 - dp * c3)
+    - c2 * dp * ec;
+  3.0 * sum + factor * series / (average * average.sqrt())
+}
+
+// The complete elliptic integral of the first kind K(m) = int_0^(pi/2) dt / sqrt(1 - m sin^2 t),
+// in terms of the parameter m = k^2 <= 1, from K(m) = pi / (2 agm(1, sqrt(1-m))).
+// Abramowitz and Stegun 17.6.
+pub fn elliptic_k(m: f64) -> f64 {
+  assert!(m <= 1.0, "K(m) is real for m <= 1");
+  if m == 1.0 {
+    return f64::INFINITY;
+  }
+  FRAC_PI_2 / agm(1.0, (1.0 - m).sqrt())
+}
+
+// The complete elliptic integral of the second kind E(m) = int_0^(pi/2) sqrt(1 - m sin^2 t) dt,
+// for m <= 1. Alongside the AGM the differences c_n = (a_(n-1) - b_(n-1)) / 2 give
+//   E(m) = K(m) (1 - sum_(n>=0) 2^(n-1) c_n^2), with c_0^2 = m.
+// Abramowitz and Stegun 17.6.4.
+pub fn elliptic_e(m: f64) -> f64 {
+  assert!(m <= 1.0, "E(m) is real for m <= 1");
+  if m == 1.0 {
+    return 1.0;
+  }
+
+  // Close to m = 1 the sum approaches 1 and cancels, so there E comes from Carlson's forms instead,
+  //   E(m) = 2 R_G(0, 1, 1-m) = (1-m) (R_F(0, 1, 1-m) + m/3 R_D(0, 1, 1-m)),
+  // with the arguments ordered so that both terms are positive. DLMF 19.25.1 and 19.21.10.
+  if m > 0.5 {
+    let y = 1.0 - m;
+    return y * (carlson_rf(0.0, 1.0, y) + m / 3.0 * carlson_rd(0.0, 1.0, y));
+  }
+
+  let (mut a, mut b) = (1.0, (1.0 - m).sqrt());
+  let mut power = 0.5;
+  let mut sum = 0.5 * m;
+  for _ in 0..MAXIMUM_ITERATIONS {
+    let c = 0.5 * (a - b);
+    (a, b) = (0.5 * (a + b), (a * b).sqrt());
+    power *= 2.0;
+    sum += power * c * c;
+    if c.abs() <= EPSILON * a {
+      break;
+    }
+  }
+  FRAC_PI_2 / a * (1.0 - sum)
+}
+
+// The complete elliptic integral of the third kind
+//   Pi(n, m) = int_0^(pi/2) dt / ((1 - n sin^2 t) sqrt(1 - m sin^2 t)),
+// for m < 1 and n != 1, taken as a Cauchy principal value for n > 1. DLMF 19.25.2.
+pub fn elliptic_pi(n: f64, m: f64) -> f64 {
+  assert!(m < 1.0 && n != 1.0, "Pi(n, m) needs m < 1 and n != 1");
+  let y = 1.0 - m;
+  carlson_rf(0.0, y, 1.0) + n / 3.0 * carlson_rj(0.0, y, 1.0, 1.0 - n)
+}
+
+// The incomplete integrals are odd in phi and quasi-periodic, I(phi + j pi) = I(phi) + 2 j I(pi/2),
+// so phi is reduced to [-pi/2, pi/2] where the Carlson forms hold, and the sine and squared cosine
+// there come out.
+fn reduce_amplitude(phi: f64) -> (f64, f64, f64) {
+  let j = (phi / PI).round();
+  let (s, c) = (phi - j * PI).sin_cos();
+  (j, s, c * c)
+}
+
+// The incomplete elliptic integral of the first kind F(phi | m) = int_0^phi dt / sqrt(1 - m sin^2 t),
+// for 1 - m sin^2 t >= 0 along the way. F(phi | m) = s R_F(c^2, 1 - m s^2, 1) with s and c the
+// sine and cosine of phi. DLMF 19.25.5.
+pub fn elliptic_f(phi: f64, m: f64) -> f64 {
+  let (j, s, c2) = reduce_amplitude(phi);
+  let value = s * carlson_rf(c2, 1.0 - m * s * s, 1.0);
+  if j == 0.0 {
+    value
+  } else {
+    value + 2.0 * j * elliptic_k(m)
+  }
+}
+
+// The incomplete elliptic integral of the second kind E(phi | m) = int_0^phi sqrt(1 - m sin^2 t) dt.
+// DLMF 19.25.9.
+pub fn elliptic_e_incomplete(phi: f64, m: f64) -> f64 {
+  let (j, s, c2) = reduce_amplitude(phi);
+  let y = 1.0 - m * s * s;
+  let value = s * carlson_rf(c2, y, 1.0) - m * s.powi(3) / 3.0 * carlson_rd(c2, y, 1.0);
+  if j == 0.0 {
+    value
+  } else {
+    value + 2.0 * j * elliptic_e(m)
+  }
+}
+
+// The incomplete elliptic integral of the third kind
+//   Pi(n; phi | m) = int_0^phi dt / ((1 - n sin^2 t) sqrt(1 - m sin^2 t)),
+// taken as a Cauchy principal value where 1 - n sin^2 t changes sign. DLMF 19.25.14.
+pub fn elliptic_pi_incomplete(n: f64, phi: f64, m: f64) -> f64 {
+  let (j, s, c2) = reduce_amplitude(phi);
+  let y = 1.0 - m * s * s;
+  let value = s * carlson_rf(c2, y, 1.0) + n * s.powi(3) / 3.0 * carlson_rj(c2, y, 1.0, 1.0 - n * s * s);
+  if j == 0.0 {
+    value
+  } else {
+    value + 2.0 * j * elliptic_pi(n, m)
+  }
+}
+
+// The Jacobi elliptic functions (sn, cn, dn)(u | m), for any real m, by descending Landen
+// transformations. For m > 1 they come from the reciprocal parameter, Abramowitz and Stegun
+// 16.11. See Numerical Recipes section 6.12.
+pub fn jacobi_elliptic(u: f64, m: f64) -> (f64, f64, f64) {
+  let mut mc = 1.0 - m;
+  if mc == 0.0 {
+    // At m = 1 they are hyperbolic functions.
+    let sech = 1.0 / u.cosh();
+    return (u.tanh(), sech, sech);
+  }
+
+  let reciprocal = mc < 0.0;
+  let mut u = u;
+  let mut scale = 1.0;
+  if reciprocal {
+    scale = m.sqrt();
+    mc /= -m;
+    u *= scale;
+  }
+
+  let mut a = 1.0;
+  let mut dn = 1.0;
+  let mut arithmetic = Vec::new();
+  let mut geometric = Vec::new();
+  let mut c;
+  loop {
+    arithmetic.push(a);
+    mc = mc.sqrt();
+    geometric.push(mc);
+    c = 0.5 * (a + mc);
+    if (a - mc).abs() <= LANDEN_TOLERANCE * a || arithmetic.len() == MAXIMUM_ITERATIONS {
+      break;
+    }
+    mc *= a;
+    a = c;
+  }
+
+  u *= c;
+  let (mut sn, mut cn) = u.sin_cos();
+  if sn != 0.0 {
+    let mut a = cn / sn;
+    c *= a;
+    for (b, g) in arithmetic.iter().zip(geometric.iter()).rev() {
+      a *= c;
+      c *= dn;
+      dn = (g + a) / (b + a);
+      a = c / b;
+    }
+    let a = 1.0 / (c * c + 1.0).sqrt();
+    sn = a.copysign(sn);
+    cn = c * sn;
+  }
+
+  if reciprocal {
+    (sn / scale, dn, cn)
+  } else {
+    (sn, cn, dn)
+  }
+}
+
+// The Jacobi amplitude am(u | m), the inverse of F(phi | m) for 0 <= m <= 1, with sn = sin am and
+// cn = cos am. It increases continuously with u, by pi every 2 K(m).
+pub fn jacobi_amplitude(u: f64, m: f64) -> f64 {
+  assert!((0.0..=1.0).contains(&m), "the Jacobi amplitude needs 0 <= m <= 1");
+  if m == 1.0 {
+    // The Gudermannian function.
+    return u.sinh().atan();
+  }
+  let (sn, cn, _) = jacobi_elliptic(u, m);
+  let principal = sn.atan2(cn);
+
+  // am(u) - pi u / (2 K) is periodic and smaller than pi / 2, which fixes the branch.
+  let linear = FRAC_PI_2 * u / elliptic_k(m);
+  principal + 2.0 * PI * ((linear - principal) / (2.0 * PI)).round()
+}
+
+// The exact period of a simple pendulum of the given length swinging with amplitude theta_0 (in
+// radians, below pi) under gravitational acceleration g,
+//   T = 4 sqrt(L / g) K(sin^2(theta_0 / 2)),
+// which reduces to 2 pi sqrt(L / g) for small swings.
+pub fn pendulum_period(length: f64, gravitational_acceleration: f64, amplitude: f64) -> f64 {
+  assert!(amplitude.abs() < PI, "a pendulum released at pi or beyond does not oscillate");
+  4.0 * (length / gravitational_acceleration).sqrt() * elliptic_k((0.5 * amplitude).sin().powi(2))
+}
+
+#[test]
+fn test_carlson() {
+  // Reference values computed to 30 digits with mpmath. The principal values for p < 0 agree with
+  // quadrature of the integral split symmetrically about its pole.
+  let cases = [
+    (carlson_rf(1.0, 2.0, 0.0), 1.311_028_777_146_059_8),
+    (carlson_rf(0.5, 1.0, 2.0), 0.968_857_653_272_452_4),
+    (carlson_rf(2.0, 3.0, 4.0), 0.584_082_841_677_151_7),
+    (carlson_rf(1.0e-10, 1.0, 3.0), 1.171_414_310_680_485),
+    (carlson_rd(0.0, 2.0, 1.0), 1.797_210_352_103_388_4),
+    (carlson_rd(2.0, 3.0, 4.0), 0.165_105_272_942_610_54),
+    (carlson_rd(0.5, 1.0e-3, 7.0), 0.282_009_172_829_257_5),
+    (carlson_rc(0.0, 0.25), PI),
+    (carlson_rc(2.25, 2.0), 2.0_f64.ln()),
+    (carlson_rc(0.25, -2.0), 0.231_049_060_186_648_45),
+    (carlson_rj(0.0, 1.0, 2.0, 3.0), 0.776_886_237_785_823_3),
+    (carlson_rj(2.0, 3.0, 4.0, 5.0), 0.142_975_796_671_567_54),
+    (carlson_rj(2.0, 3.0, 4.0, -0.5), 0.247_238_197_030_515_64),
+    (carlson_rj(0.5, 1.0, 2.0, -3.0), -0.512_074_401_175_748),
+  ];
+  for (approx, exact) in cases {
+    assert_close(approx, exact, 4.0e-15);
+  }
+
+  // R_F is symmetric, and R_J(x, y, z, z) = R_D(x, y, z).
+  assert_close(carlson_rf(4.0, 2.0, 3.0), carlson_rf(2.0, 3.0, 4.0), 4.0e-16);
+  assert_close(carlson_rj(0.5, 1.0, 2.0, 2.0), carlson_rd(0.5, 1.0, 2.0), 4.0e-15);
+}
+
+#[test]
+fn test_complete_elliptic_integrals() {
+  assert_eq!(elliptic_k(0.0), FRAC_PI_2);
+  assert_eq!(elliptic_e(0.0), FRAC_PI_2);
+  assert_eq!(elliptic_k(1.0), f64::INFINITY);
+  assert_eq!(elliptic_e(1.0), 1.0);
+
+  // Reference values computed to 30 digits with mpmath.
+  let cases = [
+    // m, K(m), E(m)
+    (-5.0, 0.955_503_927_064_044, 2.830_198_246_345_877_2),
+    (0.3, 1.713_889_448_178_791, 1.445_363_064_412_665_4),
+    (0.5, 1.854_074_677_301_371_9, 1.350_643_881_047_675_5),
+    (0.9, 2.578_092_113_348_173_3, 1.104_774_732_704_073_3),
+    (0.999_999, 8.294_051_463_601_063, 1.000_003_897_026_172_2),
+    (1.0 - 1.0e-15, 18.656_082_357_290_337, 1.000_000_000_000_009),
+  ];
+  for (m, k, e) in cases {
+    println!("m={m} K={} E={}", elliptic_k(m), elliptic_e(m));
+    assert_close(elliptic_k(m), k, 4.0e-15);
+    assert_close(elliptic_e(m), e, 4.0e-15);
+  }
+
+  // Legendre's relation E(m) K(1-m) + E(1-m) K(m) - K(m) K(1-m) = pi / 2.
+  for m in [0.01, 0.2, 0.5, 0.77, 0.99] {
+    let legendre = elliptic_e(m) * elliptic_k(1.0 - m) + elliptic_e(1.0 - m) * elliptic_k(m)
+      - elliptic_k(m) * elliptic_k(1.0 - m);
+    assert_close(legendre, FRAC_PI_2, 4.0e-15);
+  }
+
+  let cases = [
+    // n, m, Pi(n, m)
+    (0.3, 0.5, 2.250_376_821_943_947),
+    (-2.0, 0.8, 1.176_874_417_055_348_5),
+    (0.9, -1.0, 3.798_047_436_400_051_6),
+    (2.0, 0.5, -0.313_544_683_465_184_05),
+  ];
+  for (n, m, pi) in cases {
+    assert_close(elliptic_pi(n, m), pi, 4.0e-15);
+  }
+  assert_close(elliptic_pi(0.0, 0.4), elliptic_k(0.4), 4.0e-16);
+}
+
+#[test]
+fn test_incomplete_elliptic_integrals() {
+  // Reference values computed to 30 digits with mpmath.
+  let cases = [
+    // phi, m, F(phi | m), E(phi | m)
+    (0.7, 0.5, 0.728_770_305_718_190_2, 0.673_189_174_547_128_8),
+    (1.5, 0.99, 3.036_014_097_339_71, 1.008_366_245_703_958_2),
+    (-2.0, 0.3, -2.220_590_552_128_474, -1.808_964_725_363_331_2),
+    (10.0, 0.8, 14.145_226_112_695_65, 7.621_467_653_466_81),
+    (0.6, 2.0, 0.700_694_880_369_797_9, 0.524_176_356_342_695),
+  ];
+  for (phi, m, f, e) in cases {
+    println!("phi={phi} m={m} F={} E={}", elliptic_f(phi, m), elliptic_e_incomplete(phi, m));
+    assert_close(elliptic_f(phi, m), f, 4.0e-15);
+    assert_close(elliptic_e_incomplete(phi, m), e, 4.0e-15);
+  }
+
+  let cases = [
+    // n, phi, m, Pi(n; phi | m)
+    (0.4, 0.7, 0.5, 0.778_506_781_075_796),
+    (-1.5, 1.2, 0.3, 0.882_032_474_278_458),
+    (0.5, 7.0, 0.2, 10.273_094_637_196_378),
+    (3.0, 1.2, 0.6, 0.047_316_345_125_155_39),
+  ];
+  for (n, phi, m, pi) in cases {
+    assert_close(elliptic_pi_incomplete(n, phi, m), pi, 1.0e-14);
+  }
+
+  // At phi = pi/2 they are the complete integrals.
+  assert_close(elliptic_f(FRAC_PI_2, 0.6), elliptic_k(0.6), 4.0e-16);
+  assert_close(elliptic_e_incomplete(FRAC_PI_2, 0.6), elliptic_e(0.6), 4.0e-16);
+  assert_close(elliptic_pi_incomplete(0.3, FRAC_PI_2, 0.6), elliptic_pi(0.3, 0.6), 4.0e-16);
+}
+
+#[test]
+fn test_jacobi_elliptic() {
+  // Reference values computed to 30 digits with mpmath.
+  let cases = [
+    // u, m, sn, cn, dn
+    (0.5, 0.3, 0.474_215_622_711_820_6, 0.880_408_736_426_462_4, 0.965_678_964_745_951_2),
+    (2.0, 0.9, 0.981_615_869_518_493_8, 0.190_867_191_286_117_48, 0.364_399_857_626_901_7),
+    (-3.7, 0.5, -0.008_149_219_301_520_08, -0.999_966_794_561_087_3, 0.999_983_397_418_371_1),
+    (10.0, 0.999, -0.307_462_292_454_822_26, -0.951_560_265_415_924_9, 0.951_609_936_780_668),
+    (1.0, -2.0, 0.957_075_389_067_284_4, 0.289_839_092_673_342_2, 1.682_850_736_315_193),
+    (0.8, 3.0, 0.553_856_689_908_430_4, 0.832_612_014_712_541_1, 0.282_362_003_695_664_33),
+  ];
+  for (u, m, sn, cn, dn) in cases {
+    let (s, c, d) = jacobi_elliptic(u, m);
+    println!("u={u} m={m} sn={s} cn={c} dn={d}");
+    assert!((s - sn).abs() < 1.0e-14 && (c - cn).abs() < 1.0e-14 && (d - dn).abs() < 1.0e-14);
+
+    // sn^2 + cn^2 = 1 and dn^2 + m sn^2 = 1.
+    assert!((s * s + c * c - 1.0).abs() < 4.0e-16);
+    assert!((d * d + m * s * s - 1.0).abs() < 1.0e-15);
+  }
+
+  // The limits m = 0 and m = 1 are trigonometric and hyperbolic.
+  let (s, c, d) = jacobi_elliptic(0.9, 0.0);
+  assert!((s - 0.9_f64.sin()).abs() < 4.0e-16 && (c - 0.9_f64.cos()).abs() < 4.0e-16 && (d - 1.0).abs() < 4.0e-16);
+  let (s, c, d) = jacobi_elliptic(0.9, 1.0);
+  assert!(s == 0.9_f64.tanh() && c == 1.0 / 0.9_f64.cosh() && d == c);
+
+  // The amplitude inverts F, across several periods. This direction is well conditioned, since
+  // d am / du = dn <= 1.
+  for m in [0.0, 0.3, 0.9, 0.999_999, 1.0] {
+    for phi in [-7.5_f64, -0.4, 0.0, 1.1, 3.0, 12.0] {
+      if m == 1.0 && phi.abs() >= FRAC_PI_2 {
+        continue;
+      }
+      let am = jacobi_amplitude(elliptic_f(phi, m), m);
+      assert!((am - phi).abs() < 1.0e-14, "m={m} phi={phi} am={am}");
+    }
+  }
+}
+
+#[test]
+fn test_pendulum_period() {
+  use crate::utils::integrate::integrate_gaussian_quadrature_adaptive;
+
+  let (length, g) = (1.5_f64, 9.81);
+  let small = 2.0 * PI * (length / g).sqrt();
+
+  // Small swings recover the harmonic period, with the first correction theta_0^2 / 16.
+  let amplitude = 1.0e-3;
+  assert_close(pendulum_period(length, g, amplitude), small * (1.0 + amplitude * amplitude / 16.0), 1.0e-13);
+
+  // Reference values computed to 30 digits with mpmath: T / T_0 = 2 K(sin^2(theta_0 / 2)) / pi.
+  assert_close(pendulum_period(length, g, FRAC_PI_2) / small, 1.180_340_599_016_096_2, 4.0e-16);
+  // Close to theta_0 = pi the period grows logarithmically, and the rounding of sin^2(theta_0 / 2) is
+  // magnified several hundred times.
+  assert_close(pendulum_period(length, g, 179.0_f64.to_radians()) / small, 3.901_065_160_389_085_6, 1.0e-13);
+
+  // The period integral T = 4 sqrt(L / (2 g)) int_0^theta_0 dtheta / sqrt(cos theta - cos theta_0)
+  // has an inverse square root singularity at the turning point, which the substitution
+  // sin(theta / 2) = sin(theta_0 / 2) sin t removes.
+  for amplitude in [0.3_f64, 1.0, 2.0, 3.0] {
+    let k = (0.5 * amplitude).sin();
+    let integral = integrate_gaussian_quadrature_adaptive(0.0, FRAC_PI_2, 1.0e-14,
+      |t: f64| 1.0 / (1.0 - (k * t.sin()).powi(2)).sqrt()
+    );
+    let period = 4.0 * (length / g).sqrt() * integral;
+    assert_close(pendulum_period(length, g, amplitude), period, 1.0e-13);
+  }
+
+  // Exercise 5.10: in the potential V = x^4 with m = 1 the period is
+  //   T = sqrt(8) int_0^a dx / sqrt(a^4 - x^4) = 2 K(1/2) / a,
+  // against which its 20 point Gaussian quadrature of the singular integrand gives 3.648 at a = 1.
+  assert_close(2.0 * elliptic_k(0.5), 3.708_149_354_602_743_8, 4.0e-16);
+}
@@ -0,0 +1,137 @@
+use crate::utils::big_uint::BigUint;
+use crate::utils::special::gamma::{gamma, ln_gamma};
+
+// Above this ln C(n, k) overflows f64.
+const LN_MAXIMUM_FLOAT: f64 = 709.8;
+
+// n! as the nearest f64, which is infinite from 171! on.
+pub fn factorial(n: u32) -> f64 {
+  gamma(n as f64 + 1.0)
+}
+
+// ln n!, which stays finite for any n.
+pub fn ln_factorial(n: u32) -> f64 {
+  ln_gamma(n as f64 + 1.0)
+}
+
+// n! exactly, or None once it no longer fits in a u128 past 34!.
+pub fn factorial_exact(n: u32) -> Option<u128> {
+  (2..=n as u128).try_fold(1_u128, |product, k| product.checked_mul(k))
+}
+
+// n! exactly, however large.
+pub fn factorial_big(n: u32) -> BigUint {
+  let mut product = BigUint::one();
+  for k in 2..=n {
+    product.mul_small(k);
+  }
+  product
+}
+
+// The binomial coefficient C(n, k) = n! / (k! (n-k)!) as the nearest f64, zero for k > n.
+pub fn binomial(n: u32, k: u32) -> f64 {
+  if k > n {
+    return 0.0;
+  }
+  if let Some(exact) = binomial_exact(n, k) {
+    return exact as f64;
+  }
+  if ln_factorial(n) - ln_factorial(k) - ln_factorial(n - k) > LN_MAXIMUM_FLOAT {
+    return f64::INFINITY;
+  }
+  // Anything that fits in an f64 has at most 1024 bits, so the exact product stays cheap.
+  binomial_big(n, k).to_f64()
+}
+
+fn gcd(mut a: u128, mut b: u128) -> u128 {
+  while b != 0 {
+    (a, b) = (b, a % b);
+  }
+  a
+}
+
+// C(n, k) exactly, or None if it does not fit in a u128.
+pub fn binomial_exact(n: u32, k: u32) -> Option<u128> {
+  if k > n {
+    return Some(0);
+  }
+
+  // C(n-k+i, i) = C(n-k+i-1, i-1) (n-k+i) / i is an integer at every step. Cancelling the common
+  // factor of the running product and i first keeps the intermediate product from overflowing
+  // before the result does.
+  let k = k.min(n - k) as u128;
+  let m = n as u128 - k;
+  let mut result = 1_u128;
+  for i in 1..=k {
+    let common = gcd(result, i);
+    result = (result / common).checked_mul((m + i) / (i / common))?;
+  }
+  Some(result)
+}
+
+// C(n, k) exactly, however large.
+pub fn binomial_big(n: u32, k: u32) -> BigUint {
+  if k > n {
+    return BigUint::zero();
+  }
+  let k = k.min(n - k);
+  let mut result = BigUint::one();
+  for i in 1..=k {
+    result.mul_small(n - k + i);
+    let remainder = result.div_rem_small(i);
+    debug_assert_eq!(remainder, 0);
+  }
+  result
+}
+
+#[test]
+fn test_factorial() {
+  assert_eq!(factorial(0), 1.0);
+  assert_eq!(factorial(20), 2_432_902_008_176_640_000.0);
+  assert_eq!(factorial(170), 7.257_415_615_307_999e306);
+  assert_eq!(factorial(171), f64::INFINITY);
+
+  assert_eq!(factorial_exact(0), Some(1));
+  assert_eq!(factorial_exact(34), Some(295_232_799_039_604_140_847_618_609_643_520_000_000));
+  assert_eq!(factorial_exact(35), None);
+
+  assert_eq!(
+    factorial_big(100).to_string(),
+    "93326215443944152681699238856266700490715968264381621468592963895217599993229915608941463976156518286253\
+     697920827223758251185210916864000000000000000000000000"
+  );
+  assert_eq!(factorial_big(34).to_string(), factorial_exact(34).unwrap().to_string());
+  // Every factorial that fits in an f64 is correctly rounded.
+  for n in 0..=170 {
+    assert_eq!(factorial_big(n).to_f64(), factorial(n), "n={n}");
+  }
+
+  // Reference values computed to 30 digits with mpmath.
+  assert!((ln_factorial(1000) - 5_912.128_178_488_163).abs() < 1.0e-12);
+  assert!((ln_factorial(1_000_000) - 12_815_518.384_658_17).abs() < 1.0e-8);
+}
+
+#[test]
+fn test_binomial() {
+  assert_eq!(binomial_exact(52, 5), Some(2_598_960));
+  assert_eq!(binomial_exact(100, 50), Some(100_891_344_545_564_193_334_812_497_256));
+  assert_eq!(binomial_exact(3, 5), Some(0));
+  assert_eq!(binomial(3, 5), 0.0);
+
+  // C(131, 65) is the last central coefficient that fits in a u128.
+  assert_eq!(binomial_exact(131, 65).map(|c| c.to_string()), Some(binomial_big(131, 65).to_string()));
+  assert_eq!(binomial_exact(132, 66), None);
+
+  assert_eq!(binomial(1000, 500), 2.702_882_409_454_365_5e299);
+  assert_eq!(binomial(1029, 514), 1.429_820_686_498_904e308);
+  assert_eq!(binomial(1030, 515), f64::INFINITY);
+
+  // (n-k) C(n, k) = (k+1) C(n, k+1), far past the range of u128.
+  for (n, k) in [(300, 1), (300, 17), (300, 149), (1000, 998), (5000, 2600)] {
+    let mut left = binomial_big(n, k);
+    left.mul_small(n - k);
+    let mut right = binomial_big(n, k + 1);
+    right.mul_small(k + 1);
+    assert_eq!(left, right, "n={n} k={k}");
+  }
+}
@@ -0,0 +1,200 @@
+use std::f64::consts::{FRAC_PI_2, PI};
+
+use crate::utils::complex::Complex;
+#[cfg(test)]
+use crate::utils::special::assert_close;
+
+const EPSILON: f64 = 1e-16;
+const FLOAT_MINIMUM: f64 = 1e-300;
+const MAXIMUM_ITERATIONS: usize = 100;
+
+// Below this the power series is used, and above it the continued fraction. See Numerical Recipes
+// section 6.9.
+const SERIES_LIMIT: f64 = 1.5;
+
+// Above this a handful of terms of the asymptotic series reach full precision.
+const ASYMPTOTIC_LIMIT: f64 = 36.0;
+
+// Past this the auxiliary functions are smaller than half an ulp of 1/2.
+const SATURATION_LIMIT: f64 = 1.0e16;
+
+// cos(pi x^2 / 2) and sin(pi x^2 / 2). The phase is reduced modulo 2 pi exactly: x^2 is split into
+// its rounded value and rounding error, and the rounded value is reduced modulo 4 before
+// multiplying by pi/2, so the phase stays accurate even for large x.
+fn cos_sin_pi_half_square(x: f64) -> (f64, f64) {
+  let square = x * x;
+  let error = x.mul_add(x, -square);
+  let reduced = square % 4.0 + error;
+  let (sin, cos) = (FRAC_PI_2 * reduced).sin_cos();
+  (cos, sin)
+}
+
+// C(x) + i S(x) from the Taylor series sum_k (i pi x^2 / 2)^k x / (k! (2k+1)), for |x| < 1.5.
+fn fresnel_series(x: f64) -> Complex {
+  let t = FRAC_PI_2 * x * x;
+  let mut power = x;
+  let mut c = x;
+  let mut s = 0.0;
+  for k in 1..MAXIMUM_ITERATIONS {
+    power *= t / k as f64;
+    let term = power / (2 * k + 1) as f64;
+    match k % 4 {
+      0 => c += term,
+      1 => s += term,
+      2 => c -= term,
+      _ => s -= term,
+    }
+    if term < EPSILON * c.abs().max(s.abs()) {
+      break;
+    }
+  }
+  Complex::new(c, s)
+}
+
+// C(x) + i S(x) for x >= 1.5, from the continued fraction for erfc of complex argument evaluated by
+// the modified Lentz method. Numerical Recipes section 6.9.
+fn fresnel_continued_fraction(x: f64) -> Complex {
+  let b0 = Complex::new(1.0, -PI * x * x);
+  let mut b = b0;
+  let mut c = Complex::new(1.0 / FLOAT_MINIMUM, 0.0);
+  let mut d = b.recip();
+  let mut h = d;
+  let mut n = -1.0;
+  for _ in 2..MAXIMUM_ITERATIONS {
+    n += 2.0;
+    let a = -n * (n + 1.0);
+    b = b + 4.0;
+    d = (d * a + b).recip();
+    c = b + c.recip() * a;
+    let delta = c * d;
+    h *= delta;
+    if (delta - 1.0).abs() < EPSILON {
+      break;
+    }
+  }
+  h *= Complex::new(x, -x);
+
+  let (cos, sin) = cos_sin_pi_half_square(x);
+  Complex::new(0.5, 0.5) * (Complex::new(1.0, 0.0) - Complex::new(cos, sin) * h)
+}
+
+// C(x) + i S(x) for x >= 36, from the auxiliary functions
+//   f(x) ~ 1/(pi x) sum_n (-1)^n (4n-1)!! / (pi x^2)^(2n),
+//   g(x) ~ 1/(pi^2 x^3) sum_n (-1)^n (4n+1)!! / (pi x^2)^(2n),
+// with C = 1/2 + f sin(pi x^2 / 2) - g cos(pi x^2 / 2) and S = 1/2 - f cos(pi x^2 / 2) - g sin(pi x^2 / 2).
+// See Abramowitz and Stegun 7.3.9, 7.3.10, 7.3.27 and 7.3.28.
+fn fresnel_asymptotic(x: f64) -> Complex {
+  if x > SATURATION_LIMIT {
+    return Complex::new(0.5, 0.5);
+  }
+
+  let z = PI * x * x;
+  let z_squared = z * z;
+  let mut f_term = 1.0;
+  let mut g_term = 1.0;
+  let mut f = 1.0;
+  let mut g = 1.0;
+  for n in 1..MAXIMUM_ITERATIONS {
+    let n = n as f64;
+    f_term *= -(4.0 * n - 3.0) * (4.0 * n - 1.0) / z_squared;
+    g_term *= -(4.0 * n - 1.0) * (4.0 * n + 1.0) / z_squared;
+    f += f_term;
+    g += g_term;
+    if f_term.abs() < EPSILON && g_term.abs() < EPSILON {
+      break;
+    }
+  }
+  let f = f / (PI * x);
+  let g = g / (PI * z * x);
+
+  let (cos, sin) = cos_sin_pi_half_square(x);
+  Complex::new(0.5 + f * sin - g * cos, 0.5 - f * cos - g * sin)
+}
+
+// The complex Fresnel integral C(x) + i S(x) = int_0^x e^(i pi t^2 / 2) dt.
+pub fn fresnel(x: f64) -> Complex {
+  let ax = x.abs();
+  let value = if ax < SERIES_LIMIT {
+    fresnel_series(ax)
+  } else if ax < ASYMPTOTIC_LIMIT {
+    fresnel_continued_fraction(ax)
+  } else {
+    fresnel_asymptotic(ax)
+  };
+
+  // C and S are odd.
+  if x < 0.0 {
+    -value
+  } else {
+    value
+  }
+}
+
+// The Fresnel cosine integral C(x) = int_0^x cos(pi t^2 / 2) dt.
+pub fn fresnel_c(x: f64) -> f64 {
+  fresnel(x).re
+}
+
+// The Fresnel sine integral S(x) = int_0^x sin(pi t^2 / 2) dt.
+pub fn fresnel_s(x: f64) -> f64 {
+  fresnel(x).im
+}
+
+#[test]
+fn test_fresnel_reference_values() {
+  // Reference values computed to 30 digits with mpmath.
+  let cases = [
+    // x, C(x), S(x)
+    (1.0e-3, 0.000_999_999_999_999_753_3, 5.235_987_755_982_066e-10),
+    (0.5, 0.492_344_225_871_446_4, 0.064_732_432_859_999_27),
+    (1.0, 0.779_893_400_376_822_9, 0.438_259_147_390_354_76),
+    (1.499, 0.446_185_953_629_228_9, 0.697_885_465_746_720_6),
+    (1.5, 0.445_261_176_039_821_5, 0.697_504_960_082_093_1),
+    (2.5, 0.457_413_009_641_777_06, 0.619_181_755_819_592_9),
+    (7.3, 0.539_268_015_658_462_5, 0.518_947_327_858_144_2),
+    (35.9, 0.508_473_960_830_251_5, 0.497_390_778_107_045_74),
+    (36.0, 0.499_997_828_337_363_67, 0.491_158_060_317_257_8),
+    (1000.0, 0.499_999_999_898_678_8, 0.499_681_690_113_816_3),
+    (12_345.678, 0.500_023_334_695_318, 0.500_010_966_329_801_5),
+  ];
+  for (x, c_x, s_x) in cases {
+    println!("x={x} C={} S={}", fresnel_c(x), fresnel_s(x));
+    assert_close(fresnel_c(x), c_x, 1.0e-14);
+    assert_close(fresnel_s(x), s_x, 1.0e-14);
+    assert_close(fresnel_c(-x), -c_x, 1.0e-14);
+    assert_close(fresnel_s(-x), -s_x, 1.0e-14);
+  }
+
+  assert_eq!(fresnel(0.0), Complex::new(0.0, 0.0));
+  assert_eq!(fresnel(1.0e300), Complex::new(0.5, 0.5));
+}
+
+#[test]
+fn test_fresnel_diffraction_intensity() {
+  use crate::utils::integrate::integrate_gaussian_quadrature;
+
+  // Exercise 5.11: the intensity behind a straight edge is
+  //   I / I0 = ((2 C(u) + 1)^2 + (2 S(u) + 1)^2) / 8,
+  // which the exercise originally computed with a 50 point Gauss-Legendre rule. That rule is
+  // accurate while the integrand has only a few oscillations, so the two should agree there.
+  let intensity = |c: f64, s: f64| ((2.0 * c + 1.0).powi(2) + (2.0 * s + 1.0).powi(2)) / 8.0;
+
+  for k in 0..=60 {
+    let u = -3.0 + 0.1 * k as f64;
+    let c = integrate_gaussian_quadrature(0.0, u, 50, |t: f64| (PI / 2.0 * t * t).cos());
+    let s = integrate_gaussian_quadrature(0.0, u, 50, |t: f64| (PI / 2.0 * t * t).sin());
+    let z = fresnel(u);
+    assert!((intensity(z.re, z.im) - intensity(c, s)).abs() < 1.0e-12, "u={u}");
+  }
+
+  // The intensity is 1/4 at the geometric shadow edge, tends to 1 in the lit region and peaks near
+  // u = 1.2172 at about 1.3704.
+  let z = fresnel(0.0);
+  assert!((intensity(z.re, z.im) - 0.25).abs() < 1.0e-15);
+  let z = fresnel(1.0e6);
+  assert!((intensity(z.re, z.im) - 1.0).abs() < 1.0e-6);
+  let z = fresnel(-1.0e6);
+  assert!(intensity(z.re, z.im) < 1.0e-12);
+  let z = fresnel(1.2172);
+  assert!((intensity(z.re, z.im) - 1.3704).abs() < 1.0e-4);
+}
@@ -0,0 +1,147 @@
+use std::f64::consts::PI;
+
+use crate::utils::complex::Complex;
+
+// The normalized eigenfunctions of the quantum harmonic oscillator, in units where
+// hbar = m = omega = 1 so that x is measured in multiples of sqrt(hbar / (m omega)),
+//   psi_n(x) = 1/sqrt(2^n n! sqrt(pi)) H_n(x) e^(-x^2/2).
+//
+// Evaluating H_n and the normalization separately overflows long before psi_n does, since 2^n n!
+// leaves the range of f64 near n = 170 while psi_n is never larger than 1. Instead psi_n comes
+// from the recurrence for the normalized functions themselves,
+//   psi_(k+1)(x) = sqrt(2/(k+1)) x psi_k(x) - sqrt(k/(k+1)) psi_(k-1)(x),
+// with the Gaussian factor held back as a logarithm until the end, so that the tails far outside
+// the classical turning points at +-sqrt(2n+1) do not underflow to zero before the polynomial
+// part has grown to meet them.
+
+// psi_0(x) through psi_n(x).
+pub fn harmonic_oscillator_wave_functions(n: usize, x: f64) -> Vec<f64> {
+  let rescale_limit = 1.0e100;
+
+  let mut values = Vec::with_capacity(n + 1);
+  let mut ln_scales = Vec::with_capacity(n + 1);
+
+  let mut psi_previous = 0.0;
+  let mut psi = PI.powf(-0.25);
+  let mut ln_scale = -0.5 * x * x;
+  values.push(psi);
+  ln_scales.push(ln_scale);
+
+  for k in 0..n {
+    let kf = k as f64;
+    let psi_next = (2.0 / (kf + 1.0)).sqrt() * x * psi - (kf / (kf + 1.0)).sqrt() * psi_previous;
+    (psi_previous, psi) = (psi, psi_next);
+
+    if psi.abs() > rescale_limit {
+      psi /= rescale_limit;
+      psi_previous /= rescale_limit;
+      ln_scale += rescale_limit.ln();
+    }
+    values.push(psi);
+    ln_scales.push(ln_scale);
+  }
+
+  values.iter().zip(ln_scales).map(|(psi, ln_scale)| psi * ln_scale.exp()).collect()
+}
+
+// psi_n(x), which stays accurate for n in the thousands.
+pub fn harmonic_oscillator_wave_function(n: usize, x: f64) -> f64 {
+  harmonic_oscillator_wave_functions(n, x)[n]
+}
+
+// The momentum-space wave function phi_n(p) = 1/sqrt(2 pi) int psi_n(x) e^(-ipx) dx. The
+// eigenfunctions are their own Fourier transforms up to a phase, phi_n(p) = (-i)^n psi_n(p).
+pub fn harmonic_oscillator_momentum_wave_function(n: usize, p: f64) -> Complex {
+  let psi = harmonic_oscillator_wave_function(n, p);
+  match n % 4 {
+    0 => Complex::new(psi, 0.0),
+    1 => Complex::new(0.0, -psi),
+    2 => Complex::new(-psi, 0.0),
+    _ => Complex::new(0.0, psi),
+  }
+}
+
+#[test]
+fn test_harmonic_oscillator_against_closed_form() {
+  use crate::utils::special::OrthogonalPolynomial;
+
+  // The direct formula is fine while 2^n n! is small.
+  let mut normalization = PI.sqrt();
+  for n in 0..30 {
+    if n > 0 {
+      normalization *= 2.0 * n as f64;
+    }
+    for x in [-3.1, -0.4, 0.0, 1.7, 5.5] {
+      let exact = OrthogonalPolynomial::Hermite.value(n, x) * (-0.5 * x * x).exp() / normalization.sqrt();
+      let approx = harmonic_oscillator_wave_function(n, x);
+      assert!((approx - exact).abs() < 1.0e-13, "n={n} x={x} approx={approx} exact={exact}");
+    }
+  }
+}
+
+#[test]
+fn test_harmonic_oscillator_orthonormality() {
+  use crate::utils::special::OrthogonalPolynomial;
+
+  // psi_m psi_n is e^(-x^2) times a polynomial of degree m + n, which the 120 point Gauss-Hermite
+  // rule integrates exactly.
+  let (nodes, weights) = OrthogonalPolynomial::Hermite.gauss_rule(120);
+  let table: Vec<Vec<f64>> = nodes.iter().map(|&x| harmonic_oscillator_wave_functions(100, x)).collect();
+
+  for m in (0..=100).step_by(7) {
+    for n in (0..=100).step_by(11) {
+      let overlap: f64 = nodes.iter().zip(weights.iter()).zip(table.iter())
+        .map(|((x, w), psi)| w * (x * x).exp() * psi[m] * psi[n])
+        .sum();
+      let expected = if m == n { 1.0 } else { 0.0 };
+      assert!((overlap - expected).abs() < 1.0e-12, "<{m}|{n}> = {overlap}");
+    }
+  }
+}
+
+#[test]
+fn test_harmonic_oscillator_large_n() {
+  // The trapezoidal rule converges geometrically for smooth functions decaying to zero at both
+  // ends, so a fine grid gives the norm and <x^2> = n + 1/2 to high accuracy.
+  for n in [200, 1000, 3000] {
+    let turning_point = (2.0 * n as f64 + 1.0).sqrt();
+    let a = -turning_point - 12.0;
+    let h = 0.01;
+    let steps = (2.0 * -a / h) as usize;
+
+    let mut norm = 0.0;
+    let mut x_squared = 0.0;
+    for k in 0..=steps {
+      let x = a + k as f64 * h;
+      let psi = harmonic_oscillator_wave_function(n, x);
+      norm += h * psi * psi;
+      x_squared += h * x * x * psi * psi;
+    }
+    println!("n={n} norm={norm} <x^2>={x_squared}");
+    assert!((norm - 1.0).abs() < 1.0e-10);
+    assert!((x_squared - (n as f64 + 0.5)).abs() < 1.0e-8 * n as f64);
+
+    // Well outside the turning point psi_n is tiny but has not underflowed.
+    let tail = harmonic_oscillator_wave_function(n, turning_point + 8.0);
+    assert!(tail > 0.0 && tail < 1.0e-20);
+  }
+}
+
+#[test]
+fn test_harmonic_oscillator_momentum_space() {
+  // phi_n(p) against the Fourier transform of psi_n by the trapezoidal rule.
+  let h = 0.005;
+  for n in [0, 1, 2, 3, 6] {
+    for p in [-1.3, 0.0, 0.7, 2.2] {
+      let mut transform = Complex::new(0.0, 0.0);
+      for k in 0..=4000 {
+        let x = -10.0 + k as f64 * h;
+        transform += Complex::from_polar(harmonic_oscillator_wave_function(n, x) * h, -p * x);
+      }
+      transform = transform / (2.0 * PI).sqrt();
+
+      let phi = harmonic_oscillator_momentum_wave_function(n, p);
+      assert!((phi - transform).abs() < 1.0e-12, "n={n} p={p} phi={phi} transform={transform}");
+    }
+  }
+}
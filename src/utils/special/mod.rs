@@ -0,0 +1,92 @@
+pub mod airy;
+pub mod bessel;
+pub mod elliptic;
+pub mod erf;
+pub mod factorial;
+pub mod fresnel;
+pub mod gamma;
+pub mod harmonic_oscillator;
+pub mod orthopoly;
+pub mod polylog;
+pub mod thermal;
+
+pub use airy::airy;
+pub use airy::airy_ai;
+pub use airy::airy_ai_prime;
+pub use airy::airy_ai_prime_zero;
+pub use airy::airy_ai_zero;
+pub use airy::airy_bi;
+pub use airy::airy_bi_prime;
+pub use airy::airy_bi_prime_zero;
+pub use airy::airy_bi_zero;
+pub use bessel::bessel_i;
+pub use bessel::bessel_in;
+pub use bessel::bessel_j;
+pub use bessel::bessel_jn;
+pub use bessel::bessel_k;
+pub use bessel::bessel_kn;
+pub use bessel::bessel_y;
+pub use bessel::bessel_yn;
+pub use bessel::spherical_bessel_j;
+pub use bessel::spherical_bessel_y;
+pub use elliptic::agm;
+pub use elliptic::carlson_rc;
+pub use elliptic::carlson_rd;
+pub use elliptic::carlson_rf;
+pub use elliptic::carlson_rj;
+pub use elliptic::elliptic_e;
+pub use elliptic::elliptic_e_incomplete;
+pub use elliptic::elliptic_f;
+pub use elliptic::elliptic_k;
+pub use elliptic::elliptic_pi;
+pub use elliptic::elliptic_pi_incomplete;
+pub use elliptic::jacobi_amplitude;
+pub use elliptic::jacobi_elliptic;
+pub use elliptic::pendulum_period;
+pub use erf::dawson;
+pub use erf::erf;
+pub use erf::erf_inv;
+pub use erf::erfc;
+pub use erf::erfc_inv;
+pub use erf::erfcx;
+pub use factorial::binomial;
+pub use factorial::binomial_big;
+pub use factorial::binomial_exact;
+pub use factorial::factorial;
+pub use factorial::factorial_big;
+pub use factorial::factorial_exact;
+pub use factorial::ln_factorial;
+pub use fresnel::fresnel;
+pub use fresnel::fresnel_c;
+pub use fresnel::fresnel_s;
+pub use gamma::beta;
+pub use gamma::digamma;
+pub use gamma::gamma;
+pub use gamma::ln_beta;
+pub use gamma::ln_gamma;
+pub use gamma::regularized_beta;
+pub use gamma::regularized_gamma_p;
+pub use gamma::regularized_gamma_q;
+pub use harmonic_oscillator::harmonic_oscillator_momentum_wave_function;
+pub use harmonic_oscillator::harmonic_oscillator_wave_function;
+pub use harmonic_oscillator::harmonic_oscillator_wave_functions;
+pub use orthopoly::associated_legendre;
+pub use orthopoly::associated_legendre_derivative;
+pub use orthopoly::associated_legendre_roots;
+pub use orthopoly::associated_legendre_sequence;
+pub use orthopoly::OrthogonalPolynomial;
+pub use polylog::dirichlet_eta;
+pub use polylog::polylog;
+pub use polylog::zeta;
+pub use thermal::bose_einstein_integral;
+pub use thermal::bose_einstein_integral_incomplete;
+pub use thermal::debye;
+pub use thermal::fermi_dirac_integral;
+pub use thermal::fermi_dirac_integral_incomplete;
+
+// The relative error check shared by the tests of the special functions.
+#[cfg(test)]
+pub(crate) fn assert_close(approx: f64, exact: f64, tolerance: f64) {
+  let error = (approx - exact).abs() / exact.abs().max(f64::MIN_POSITIVE);
+  assert!(error <= tolerance, "approx={approx} exact={exact} error={error}");
+}
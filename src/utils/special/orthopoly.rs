@@ -0,0 +1,439 @@
+use std::f64::consts::PI;
+
+use crate::utils::special::gamma::{gamma, ln_gamma};
+
+// The classical orthogonal polynomials. Every family satisfies a three-term recurrence
+//   p_(k+1)(x) = (a_k x + b_k) p_k(x) - c_k p_(k-1)(x),  p_0 = 1,
+// which is how they are evaluated, forwards in k, since that direction is stable on the interval
+// of orthogonality. Differentiating the recurrence gives one for the derivatives too, which works
+// at the ends of the interval where the usual closed forms divide by 1 - x^2.
+//
+// The roots are the eigenvalues of the symmetric tridiagonal Jacobi matrix of the recurrence (the
+// Golub-Welsch algorithm), polished by a Newton step, and the Gaussian quadrature weights are
+// the Christoffel numbers. See section 5.6.2 and appendix E.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OrthogonalPolynomial {
+  // P_n on [-1, 1] with weight 1.
+  Legendre,
+  // The physicists' H_n on the real line with weight e^(-x^2), H_n = 2^n x^n + ...
+  Hermite,
+  // The probabilists' He_n on the real line with weight e^(-x^2/2), He_n = x^n + ...
+  HermiteProbabilists,
+  // L_n on [0, oo) with weight e^(-x).
+  Laguerre,
+  // L_n^(alpha) on [0, oo) with weight x^alpha e^(-x), for alpha > -1.
+  GeneralizedLaguerre(f64),
+  // T_n(cos theta) = cos(n theta) on [-1, 1] with weight 1/sqrt(1 - x^2).
+  ChebyshevT,
+  // U_n(cos theta) = sin((n+1) theta) / sin(theta) on [-1, 1] with weight sqrt(1 - x^2).
+  ChebyshevU,
+  // P_n^(alpha, beta) on [-1, 1] with weight (1-x)^alpha (1+x)^beta, for alpha, beta > -1.
+  Jacobi(f64, f64),
+}
+
+use OrthogonalPolynomial::*;
+
+impl OrthogonalPolynomial {
+  // The coefficients (a_k, b_k, c_k) of the recurrence for p_(k+1).
+  fn recurrence(self, k: usize) -> (f64, f64, f64) {
+    let kf = k as f64;
+    match self {
+      Legendre => ((2.0 * kf + 1.0) / (kf + 1.0), 0.0, kf / (kf + 1.0)),
+      Hermite => (2.0, 0.0, 2.0 * kf),
+      HermiteProbabilists => (1.0, 0.0, kf),
+      Laguerre => GeneralizedLaguerre(0.0).recurrence(k),
+      GeneralizedLaguerre(alpha) => (-1.0 / (kf + 1.0), (2.0 * kf + 1.0 + alpha) / (kf + 1.0), (kf + alpha) / (kf + 1.0)),
+      ChebyshevT => (if k == 0 { 1.0 } else { 2.0 }, 0.0, 1.0),
+      ChebyshevU => (2.0, 0.0, 1.0),
+      Jacobi(alpha, beta) => {
+        let ab = alpha + beta;
+        if k == 0 {
+          return (0.5 * (ab + 2.0), 0.5 * (alpha - beta), 0.0);
+        }
+        let s = 2.0 * kf + ab;
+        let denominator = 2.0 * (kf + 1.0) * (kf + ab + 1.0) * s;
+        (
+          (s + 1.0) * (s + 2.0) * s / denominator,
+          (s + 1.0) * (alpha * alpha - beta * beta) / denominator,
+          2.0 * (kf + alpha) * (kf + beta) * (s + 2.0) / denominator,
+        )
+      },
+    }
+  }
+
+  // The interval of orthogonality.
+  pub fn interval(self) -> (f64, f64) {
+    match self {
+      Hermite | HermiteProbabilists => (f64::NEG_INFINITY, f64::INFINITY),
+      Laguerre | GeneralizedLaguerre(_) => (0.0, f64::INFINITY),
+      _ => (-1.0, 1.0),
+    }
+  }
+
+  // The weight function w(x) with int w(x) p_m(x) p_n(x) dx = 0 for m != n.
+  pub fn weight(self, x: f64) -> f64 {
+    match self {
+      Legendre => 1.0,
+      Hermite => (-x * x).exp(),
+      HermiteProbabilists => (-0.5 * x * x).exp(),
+      Laguerre => (-x).exp(),
+      GeneralizedLaguerre(alpha) => x.powf(alpha) * (-x).exp(),
+      ChebyshevT => 1.0 / (1.0 - x * x).sqrt(),
+      ChebyshevU => (1.0 - x * x).sqrt(),
+      Jacobi(alpha, beta) => (1.0 - x).powf(alpha) * (1.0 + x).powf(beta),
+    }
+  }
+
+  // The integral of the weight function over the interval.
+  fn weight_integral(self) -> f64 {
+    match self {
+      Legendre => 2.0,
+      Hermite => PI.sqrt(),
+      HermiteProbabilists => (2.0 * PI).sqrt(),
+      Laguerre => 1.0,
+      GeneralizedLaguerre(alpha) => gamma(alpha + 1.0),
+      ChebyshevT => PI,
+      ChebyshevU => 0.5 * PI,
+      Jacobi(alpha, beta) => {
+        let ab = alpha + beta;
+        ((ab + 1.0) * 2.0_f64.ln() + ln_gamma(alpha + 1.0) + ln_gamma(beta + 1.0) - ln_gamma(ab + 2.0)).exp()
+      },
+    }
+  }
+
+  // The diagonal d_k and off-diagonal e_(k+1) of the symmetric Jacobi matrix, whose orthonormal
+  // recurrence is e_(k+1) q_(k+1) = (x - d_k) q_k - e_k q_(k-1).
+  fn jacobi_matrix_entries(self, k: usize) -> (f64, f64) {
+    let (a, b, _) = self.recurrence(k);
+    let (a_next, _, c_next) = self.recurrence(k + 1);
+    (-b / a, (c_next / (a * a_next)).sqrt())
+  }
+
+  // p_n(x) and p_n'(x).
+  pub fn value_and_derivative(self, n: usize, x: f64) -> (f64, f64) {
+    let mut p_previous = 0.0;
+    let mut p = 1.0;
+    let mut dp_previous = 0.0;
+    let mut dp = 0.0;
+    for k in 0..n {
+      let (a, b, c) = self.recurrence(k);
+      let p_next = (a * x + b) * p - c * p_previous;
+      let dp_next = a * p + (a * x + b) * dp - c * dp_previous;
+      (p_previous, p) = (p, p_next);
+      (dp_previous, dp) = (dp, dp_next);
+    }
+    (p, dp)
+  }
+
+  pub fn value(self, n: usize, x: f64) -> f64 {
+    self.value_and_derivative(n, x).0
+  }
+
+  pub fn derivative(self, n: usize, x: f64) -> f64 {
+    self.value_and_derivative(n, x).1
+  }
+
+  // p_0(x) through p_n(x), for the cost of evaluating p_n alone.
+  pub fn sequence(self, n: usize, x: f64) -> Vec<f64> {
+    let mut values = Vec::with_capacity(n + 1);
+    values.push(1.0);
+    let mut p_previous = 0.0;
+    for k in 0..n {
+      let (a, b, c) = self.recurrence(k);
+      let p = values[k];
+      values.push((a * x + b) * p - c * p_previous);
+      p_previous = p;
+    }
+    values
+  }
+
+  // Walks the orthonormal recurrence up to degree n at x, returning q_n, q_n' and the sum of
+  // q_k^2 for k < n, along with the natural log of the factor all three have been scaled down by
+  // to keep them finite. For the Hermite and Laguerre families the q_k grow exponentially with x.
+  fn orthonormal_recurrence(self, n: usize, x: f64) -> (f64, f64, f64, f64) {
+    let rescale_limit = 1.0e100;
+    let mut q_previous = 0.0;
+    let mut q = 1.0;
+    let mut dq_previous = 0.0;
+    let mut dq = 0.0;
+    let mut sum_of_squares = 0.0;
+    let mut ln_scale = 0.0;
+    let mut e = 0.0;
+    for k in 0..n {
+      sum_of_squares += q * q;
+      let (d, e_next) = self.jacobi_matrix_entries(k);
+      let q_next = ((x - d) * q - e * q_previous) / e_next;
+      let dq_next = (q + (x - d) * dq - e * dq_previous) / e_next;
+      (q_previous, q) = (q, q_next);
+      (dq_previous, dq) = (dq, dq_next);
+      e = e_next;
+
+      if q.abs() > rescale_limit || dq.abs() > rescale_limit {
+        let factor = 1.0 / rescale_limit;
+        q *= factor;
+        q_previous *= factor;
+        dq *= factor;
+        dq_previous *= factor;
+        sum_of_squares *= factor * factor;
+        ln_scale += rescale_limit.ln();
+      }
+    }
+    (q, dq, sum_of_squares, ln_scale)
+  }
+
+  // The n roots of p_n in increasing order.
+  pub fn roots(self, n: usize) -> Vec<f64> {
+    let (diagonal, off_diagonal): (Vec<f64>, Vec<f64>) = (0..n).map(|k| self.jacobi_matrix_entries(k)).unzip();
+    let mut roots = symmetric_tridiagonal_eigenvalues(diagonal, off_diagonal[..n.saturating_sub(1)].to_vec());
+
+    // The eigenvalues are good to eps times the size of the matrix, which a Newton step on the
+    // orthonormal polynomial turns into eps relative to each root.
+    for root in roots.iter_mut() {
+      let (q, dq, _, _) = self.orthonormal_recurrence(n, *root);
+      if dq != 0.0 {
+        *root -= q / dq;
+      }
+    }
+    roots
+  }
+
+  // The n point Gaussian quadrature rule for this family's weight function, as (nodes, weights)
+  // with int w(x) f(x) dx ~ sum_i weights[i] f(nodes[i]), exact when f is a polynomial of degree
+  // below 2n.
+  pub fn gauss_rule(self, n: usize) -> (Vec<f64>, Vec<f64>) {
+    let nodes = self.roots(n);
+    let ln_weight_integral = self.weight_integral().ln();
+    let weights = nodes.iter().map(|&x| {
+      // The Christoffel number mu_0 / sum_(k<n) q_k(x)^2.
+      let (_, _, sum_of_squares, ln_scale) = self.orthonormal_recurrence(n, x);
+      (ln_weight_integral - sum_of_squares.ln() - 2.0 * ln_scale).exp()
+    }).collect();
+    (nodes, weights)
+  }
+}
+
+// The eigenvalues of the symmetric tridiagonal matrix with the given diagonal and off-diagonal,
+// in increasing order, by the QL algorithm with implicit shifts. See the tqli routine of
+// Numerical Recipes, section 11.4.
+fn symmetric_tridiagonal_eigenvalues(diagonal: Vec<f64>, off_diagonal: Vec<f64>) -> Vec<f64> {
+  let n = diagonal.len();
+  let mut d = diagonal;
+  let mut e = off_diagonal;
+  e.resize(n, 0.0);
+
+  for l in 0..n {
+    let mut iterations = 0;
+    loop {
+      // Look for a negligible off-diagonal element to split the matrix.
+      let mut m = l;
+      while m + 1 < n {
+        let dd = d[m].abs() + d[m + 1].abs();
+        if e[m].abs() <= f64::EPSILON * dd {
+          break;
+        }
+        m += 1;
+      }
+      if m == l {
+        break;
+      }
+
+      iterations += 1;
+      assert!(iterations < 100, "the QL iteration did not converge");
+
+      let mut g = (d[l + 1] - d[l]) / (2.0 * e[l]);
+      let mut r = g.hypot(1.0);
+      g = d[m] - d[l] + e[l] / (g + r.copysign(g));
+      let mut s = 1.0;
+      let mut c = 1.0;
+      let mut p = 0.0;
+      let mut deflated = false;
+      for i in (l..m).rev() {
+        let f = s * e[i];
+        let b = c * e[i];
+        r = f.hypot(g);
+        e[i + 1] = r;
+        if r == 0.0 {
+          d[i + 1] -= p;
+          e[m] = 0.0;
+          deflated = true;
+          break;
+        }
+        s = f / r;
+        c = g / r;
+        g = d[i + 1] - p;
+        r = (d[i] - g) * s + 2.0 * c * b;
+        p = s * r;
+        d[i + 1] = g + p;
+        g = c * r - b;
+      }
+      if deflated {
+        continue;
+      }
+      d[l] -= p;
+      e[l] = g;
+      e[m] = 0.0;
+    }
+  }
+
+  d.sort_by(f64::total_cmp);
+  d
+}
+
+// The associated Legendre function P_l^m(x) for 0 <= m <= l and -1 <= x <= 1, including the
+// Condon-Shortley phase (-1)^m, so that P_1^1(x) = -sqrt(1 - x^2). The recurrence in l starts from
+// P_m^m = (-1)^m (2m-1)!! (1-x^2)^(m/2), which overflows for m beyond about 150.
+pub fn associated_legendre(l: usize, m: usize, x: f64) -> f64 {
+  associated_legendre_sequence(l, m, x)[l]
+}
+
+// P_0^m(x) through P_l^m(x), which are zero for l < m.
+pub fn associated_legendre_sequence(l: usize, m: usize, x: f64) -> Vec<f64> {
+  assert!((-1.0..=1.0).contains(&x), "associated Legendre functions are defined on [-1, 1], not at {x}");
+
+  let mut values = vec![0.0; l + 1];
+  if m > l {
+    return values;
+  }
+
+  let sine = ((1.0 - x) * (1.0 + x)).sqrt();
+  let mut p = 1.0;
+  for k in 0..m {
+    p *= -((2 * k + 1) as f64) * sine;
+  }
+  values[m] = p;
+
+  let mut p_previous = 0.0;
+  for k in m..l {
+    let kf = k as f64;
+    let mf = m as f64;
+    let p_next = ((2.0 * kf + 1.0) * x * p - (kf + mf) * p_previous) / (kf - mf + 1.0);
+    p_previous = p;
+    p = p_next;
+    values[k + 1] = p;
+  }
+  values
+}
+
+// d/dx P_l^m(x) for -1 < x < 1, from (x^2 - 1) P_l^m' = l x P_l^m - (l+m) P_(l-1)^m. At x = +-1
+// the derivative is infinite for odd m, so the ends are only allowed for m = 0.
+pub fn associated_legendre_derivative(l: usize, m: usize, x: f64) -> f64 {
+  if m == 0 {
+    return Legendre.derivative(l, x);
+  }
+  assert!(x.abs() < 1.0, "the derivative of P_l^m is only defined for |x| < 1 when m > 0, not at {x}");
+  if m > l {
+    return 0.0;
+  }
+
+  let values = associated_legendre_sequence(l, m, x);
+  let previous = if l > 0 { values[l - 1] } else { 0.0 };
+  ((l as f64) * x * values[l] - ((l + m) as f64) * previous) / (x * x - 1.0)
+}
+
+// The l - m roots of P_l^m inside (-1, 1) in increasing order. Since P_l^m is (1-x^2)^(m/2) times
+// the m-th derivative of P_l, these are the roots of the Jacobi polynomial P_(l-m)^(m, m).
+pub fn associated_legendre_roots(l: usize, m: usize) -> Vec<f64> {
+  if m > l {
+    return Vec::new();
+  }
+  Jacobi(m as f64, m as f64).roots(l - m)
+}
+
+#[test]
+fn test_orthogonal_polynomials_low_orders() {
+  let x: f64 = 0.37;
+  let cases = [
+    (Legendre, 3, 0.5 * (5.0 * x.powi(3) - 3.0 * x)),
+    (Hermite, 4, 16.0 * x.powi(4) - 48.0 * x * x + 12.0),
+    (HermiteProbabilists, 4, x.powi(4) - 6.0 * x * x + 3.0),
+    (Laguerre, 3, (-x.powi(3) + 9.0 * x * x - 18.0 * x + 6.0) / 6.0),
+    (GeneralizedLaguerre(1.5), 2, 0.5 * x * x - 3.5 * x + 0.5 * 2.5 * 3.5),
+    (ChebyshevT, 5, (5.0 * x.acos()).cos()),
+    (ChebyshevU, 5, (6.0 * x.acos()).sin() / x.acos().sin()),
+    (Jacobi(0.0, 0.0), 3, 0.5 * (5.0 * x.powi(3) - 3.0 * x)),
+    (Jacobi(1.0, 2.0), 1, 0.5 * (1.0 - 2.0) + 0.5 * (1.0 + 2.0 + 2.0) * x),
+  ];
+
+  for (family, n, exact) in cases {
+    let approx = family.value(n, x);
+    println!("{family:?} n={n} approx={approx} exact={exact}");
+    assert!((approx - exact).abs() < 1.0e-14 * exact.abs().max(1.0));
+    assert_eq!(family.sequence(n, x)[n], approx);
+  }
+
+  // Derivatives against the finite difference module, including the ends of [-1, 1].
+  use crate::utils::differentiate::differentiate_ridders;
+  for family in [Legendre, Hermite, GeneralizedLaguerre(0.5), ChebyshevT, ChebyshevU, Jacobi(0.5, -0.3)] {
+    for x in [-1.0, -0.2, 0.6, 1.0] {
+      let exact = differentiate_ridders(x, 0.1, |x| family.value(7, x));
+      let approx = family.derivative(7, x);
+      assert!((approx - exact.value).abs() < 1.0e-9 * exact.value.abs().max(1.0), "{family:?} at {x}");
+    }
+  }
+  assert!((Legendre.derivative(10, 1.0) - 55.0).abs() < 1.0e-13);
+}
+
+#[test]
+fn test_gauss_rules_integrate_moments_exactly() {
+  // int w(x) x^k dx for each weight function.
+  fn moment(family: OrthogonalPolynomial, k: usize) -> f64 {
+    let double_factorial = |k: usize| (1..=k).rev().step_by(2).map(|j| j as f64).product::<f64>();
+    let even = |k: usize, value: f64| if k.is_multiple_of(2) { value } else { 0.0 };
+    match family {
+      Legendre => even(k, 2.0 / (k + 1) as f64),
+      Hermite => even(k, gamma((k as f64 + 1.0) / 2.0)),
+      HermiteProbabilists => even(k, (2.0 * PI).sqrt() * double_factorial(k.saturating_sub(1))),
+      GeneralizedLaguerre(alpha) => gamma(k as f64 + alpha + 1.0),
+      ChebyshevT => even(k, PI * double_factorial(k.saturating_sub(1)) / double_factorial(k)),
+      // int (1-x) x^k dx over [-1, 1]
+      Jacobi(1.0, 0.0) => moment(Legendre, k) - moment(Legendre, k + 1),
+      _ => unreachable!("no closed-form moment for {family:?}"),
+    }
+  }
+
+  for family in [Legendre, Hermite, HermiteProbabilists, GeneralizedLaguerre(0.5), ChebyshevT, Jacobi(1.0, 0.0)] {
+    let n = 12;
+    let (nodes, weights) = family.gauss_rule(n);
+    assert_eq!(nodes.len(), n);
+    assert!(nodes.windows(2).all(|pair| pair[0] < pair[1]));
+    for &x in nodes.iter() {
+      let (p, dp) = family.value_and_derivative(n, x);
+      assert!((p / dp).abs() < 1.0e-14 * x.abs().max(1.0), "{family:?} root {x}");
+    }
+
+    for k in 0..2 * n {
+      let approx: f64 = nodes.iter().zip(weights.iter()).map(|(x, w)| w * x.powi(k as i32)).sum();
+      let exact = moment(family, k);
+      let scale: f64 = nodes.iter().zip(weights.iter()).map(|(x, w)| (w * x.powi(k as i32)).abs()).sum();
+      assert!((approx - exact).abs() < 1.0e-13 * scale, "{family:?} k={k} approx={approx} exact={exact}");
+    }
+  }
+
+  // Large rules stay finite, even where the Hermite weights underflow.
+  let (nodes, weights) = Hermite.gauss_rule(400);
+  let total: f64 = weights.iter().sum();
+  assert!((total - PI.sqrt()).abs() < 1.0e-13);
+  assert!(nodes.iter().all(|x| x.is_finite()));
+  let (nodes, _) = Legendre.gauss_rule(1000);
+  assert!((nodes[999] - 0.999_997_111_298_075_5).abs() < 1.0e-15);
+}
+
+#[test]
+fn test_associated_legendre() {
+  let x: f64 = -0.45;
+  let s = (1.0 - x * x).sqrt();
+  assert!((associated_legendre(1, 1, x) + s).abs() < 1.0e-15);
+  assert!((associated_legendre(2, 1, x) + 3.0 * x * s).abs() < 1.0e-15);
+  assert!((associated_legendre(3, 2, x) - 15.0 * x * (1.0 - x * x)).abs() < 1.0e-14);
+  assert!((associated_legendre(4, 0, x) - Legendre.value(4, x)).abs() < 1.0e-15);
+  assert_eq!(associated_legendre(2, 3, x), 0.0);
+
+  let derivative = associated_legendre_derivative(3, 2, x);
+  assert!((derivative - 15.0 * (1.0 - 3.0 * x * x)).abs() < 1.0e-13);
+
+  let roots = associated_legendre_roots(5, 2);
+  assert_eq!(roots.len(), 3);
+  for x in roots {
+    assert!(associated_legendre(5, 2, x).abs() < 1.0e-13);
+  }
+}
@@ -0,0 +1,194 @@
+use std::f64::consts::PI;
+
+use crate::utils::special::gamma::gamma;
+use crate::utils::special::polylog::{polylog_exp, polylog_negative_exp, zeta};
+#[cfg(test)]
+use crate::utils::special::assert_close;
+
+const EPSILON: f64 = 1e-16;
+const MAXIMUM_ITERATIONS: usize = 100;
+
+// Below this the Debye function comes from its power series, convergent for x < 2 pi, and above it
+// from the complete integral less the tail.
+const DEBYE_SERIES_LIMIT: f64 = 2.0;
+
+// The Debye function D_n(x) = n / x^n int_0^x t^n / (e^t - 1) dt, for n >= 1. D_3 gives the Debye
+// model's heat capacity, and D_n(0) = 1.
+pub fn debye(n: u32, x: f64) -> f64 {
+  assert!(n >= 1, "the Debye function is defined for n >= 1");
+  assert!(x >= 0.0, "the Debye function is defined for x >= 0");
+
+  let nf = n as f64;
+  if x < DEBYE_SERIES_LIMIT {
+    // t / (e^t - 1) = sum_k B_k t^k / k! integrated term by term, with
+    // B_2m / (2m)! = (-1)^(m+1) 2 zeta(2m) / (2 pi)^(2m). Abramowitz and Stegun 27.1.1.
+    let x_over_two_pi_squared = (x / (2.0 * PI)).powi(2);
+    let mut power = 1.0;
+    let mut sum = 1.0 - nf * x / (2.0 * (nf + 1.0));
+    for m in 1..MAXIMUM_ITERATIONS {
+      let mf = m as f64;
+      power *= -x_over_two_pi_squared;
+      let term = -2.0 * nf * power * zeta(2.0 * mf) / (2.0 * mf + nf);
+      sum += term;
+      if term.abs() < EPSILON * sum.abs() {
+        break;
+      }
+    }
+    return sum;
+  }
+
+  // int_0^x = n! zeta(n+1) - int_x^inf, where integrating the tail by parts gives
+  //   int_x^inf t^n / (e^t - 1) dt = n! sum_(m<=n) x^m / m! Li_(n+1-m)(e^-x).
+  // Abramowitz and Stegun 27.1.2.
+  let n_factorial = gamma(nf + 1.0);
+  let complete = n_factorial * zeta(nf + 1.0);
+  let tail = n_factorial * incomplete_sum(n, x, |s| polylog_exp(s, -x));
+  nf * (complete - tail) / x.powi(n as i32)
+}
+
+// sum_(m<=j) x^m / m! Li_(j+1-m)(w), with Li_s(w) supplied by the caller.
+fn incomplete_sum(j: u32, x: f64, polylog: impl Fn(f64) -> f64) -> f64 {
+  let mut coefficient = 1.0;
+  let mut sum = 0.0;
+  for m in 0..=j {
+    sum += coefficient * polylog((j + 1 - m) as f64);
+    coefficient *= x / (m + 1) as f64;
+  }
+  sum
+}
+
+// The complete Bose-Einstein integral
+//   G_j(eta) = 1/Gamma(j+1) int_0^inf t^j / (e^(t-eta) - 1) dt = Li_(j+1)(e^eta),
+// for eta <= 0 and j > -1, continued analytically to other j. G_j(0) = zeta(j+1), so for instance
+// int_0^inf x^3 / (e^x - 1) dx = 3! G_3(0) = pi^4 / 15.
+pub fn bose_einstein_integral(j: f64, eta: f64) -> f64 {
+  assert!(eta <= 0.0, "the Bose-Einstein integral diverges for eta > 0");
+  polylog_exp(j + 1.0, eta)
+}
+
+// The incomplete Bose-Einstein integral 1/j! int_x^inf t^j / (e^(t-eta) - 1) dt, for integer j >= 0
+// and x >= max(eta, 0).
+pub fn bose_einstein_integral_incomplete(j: u32, eta: f64, x: f64) -> f64 {
+  assert!(x >= 0.0 && x >= eta, "the incomplete Bose-Einstein integral needs x >= max(eta, 0)");
+  incomplete_sum(j, x, |s| polylog_exp(s, eta - x))
+}
+
+// The complete Fermi-Dirac integral
+//   F_j(eta) = 1/Gamma(j+1) int_0^inf t^j / (e^(t-eta) + 1) dt = -Li_(j+1)(-e^eta),
+// for j > -1, continued analytically to other j. It stays finite for any eta, and for large eta it
+// grows like eta^(j+1) / Gamma(j+2).
+pub fn fermi_dirac_integral(j: f64, eta: f64) -> f64 {
+  -polylog_negative_exp(j + 1.0, eta)
+}
+
+// The incomplete Fermi-Dirac integral 1/j! int_x^inf t^j / (e^(t-eta) + 1) dt, for integer j >= 0
+// and x >= 0.
+pub fn fermi_dirac_integral_incomplete(j: u32, eta: f64, x: f64) -> f64 {
+  assert!(x >= 0.0, "the incomplete Fermi-Dirac integral needs x >= 0");
+  -incomplete_sum(j, x, |s| polylog_negative_exp(s, eta - x))
+}
+
+#[test]
+fn test_debye() {
+  assert_eq!(debye(3, 0.0), 1.0);
+
+  // Reference values computed to 30 digits with mpmath.
+  let cases = [
+    // n, x, D_n(x)
+    (1, 0.5, 0.881_927_156_790_605_5),
+    (1, 1.9999, 0.606_961_980_430_764_2),
+    (1, 2.0, 0.606_947_284_609_810_1),
+    (2, 3.0, 0.342_613_960_607_863_53),
+    (3, 0.1, 0.962_999_940_487_211_2),
+    (3, 1.0, 0.674_415_564_077_814_7),
+    (3, 5.0, 0.117_597_411_799_933_97),
+    (4, 30.0, 0.000_122_895_140_920_778_55),
+    (5, 100.0, 6.104_058_371_906_695e-8),
+  ];
+  for (n, x, d) in cases {
+    println!("n={n} x={x} D={}", debye(n, x));
+    assert_close(debye(n, x), d, 4.0e-15);
+  }
+
+  // Exercise 5.9: the heat capacity of 1000 cm^3 of aluminum at 290K, which the exercise records as
+  // 2234.979772391354 J/K using a 50 point Gaussian quadrature of x^4 e^x / (e^x - 1)^2. Integrating
+  // that by parts gives 4 int_0^b x^3 / (e^x - 1) dx - b^4 / (e^b - 1) = 4/3 b^3 D_3(b) - b^4 / (e^b - 1).
+  let boltzmanns_constant = 1.380649e-23;
+  let (volume, number_density, debye_temperature, temperature) = (1.0e-3, 6.002e28, 428.0, 290.0);
+  let b: f64 = debye_temperature / temperature;
+  let integral = 4.0 / 3.0 * b.powi(3) * debye(3, b) - b.powi(4) / b.exp_m1();
+  let heat_capacity =
+    9.0 * volume * number_density * boltzmanns_constant * (temperature / debye_temperature).powi(3) * integral;
+  assert_close(heat_capacity, 2_234.979_772_391_354, 1.0e-14);
+}
+
+#[test]
+fn test_bose_einstein_and_fermi_dirac() {
+  // Exercise 5.12: int_0^inf x^3 / (e^x - 1) dx = pi^4 / 15, which the exercise finds as
+  // 6.493939400514869 with 50 point Gaussian quadrature and 6.493939402266829 adaptively.
+  let integral = 6.0 * bose_einstein_integral(3.0, 0.0);
+  assert_close(integral, PI.powi(4) / 15.0, 4.0e-16);
+  assert!((integral - 6.493_939_402_266_829).abs() < 1.0e-15);
+  assert!((integral - 6.493_939_400_514_869).abs() < 2.0e-9);
+
+  // F_0(eta) = ln(1 + e^eta) and G_0(eta) = -ln(1 - e^eta).
+  for eta in [-30.0, -2.0, -0.3, 0.4, 3.0, 50.0, 800.0] {
+    assert_close(fermi_dirac_integral(0.0, eta), eta.max(0.0) + (-eta.abs()).exp().ln_1p(), 4.0e-15);
+    if eta < 0.0 {
+      assert_close(bose_einstein_integral(0.0, eta), -(-eta.exp()).ln_1p(), 4.0e-15);
+    }
+  }
+
+  // Reference values computed to 30 digits with mpmath.
+  let cases = [
+    // j, eta, G_j(eta)
+    (0.5, -2.0, 0.142_334_337_637_435_75),
+    (1.5, -0.1, 1.147_715_706_869_265_8),
+    (3.0, 0.0, 1.082_323_233_711_138_1),
+    (2.0, -0.5, 0.663_933_100_544_827_7),
+  ];
+  for (j, eta, g) in cases {
+    assert_close(bose_einstein_integral(j, eta), g, 4.0e-15);
+  }
+  let cases = [
+    // j, eta, F_j(eta)
+    (-0.5, 1.0, 1.027_057_125_474_350_8),
+    (0.5, -3.0, 0.048_933_705_696_495_78),
+    (0.5, 0.0, 0.765_147_024_625_408),
+    (0.5, 4.0, 6.511_567_592_754_791),
+    (1.5, 25.0, 949.592_217_378_990_7),
+    (2.5, 200.0, 9_730_092.730_100_917),
+    (3.0, -1.0, 0.359_973_276_836_331),
+  ];
+  for (j, eta, f) in cases {
+    println!("j={j} eta={eta} F={}", fermi_dirac_integral(j, eta));
+    assert_close(fermi_dirac_integral(j, eta), f, 4.0e-15);
+  }
+}
+
+#[test]
+fn test_incomplete_bose_einstein_and_fermi_dirac() {
+  // From x = 0 the incomplete integrals are the complete ones.
+  assert_close(bose_einstein_integral_incomplete(2, -0.7, 0.0), bose_einstein_integral(2.0, -0.7), 4.0e-16);
+  assert_close(fermi_dirac_integral_incomplete(3, 1.3, 0.0), fermi_dirac_integral(3.0, 1.3), 4.0e-16);
+
+  // Reference values computed to 30 digits with mpmath.
+  let cases = [
+    // j, eta, x, integral
+    (2, -1.0, 0.5, 0.379_865_322_563_796_96),
+    (1, 0.5, 3.0, 0.340_785_386_024_605_84),
+    (3, -0.2, 10.0, 0.008_462_576_776_760_412),
+  ];
+  for (j, eta, x, g) in cases {
+    assert_close(bose_einstein_integral_incomplete(j, eta, x), g, 4.0e-15);
+  }
+  let cases = [
+    (2, 1.0, 0.5, 2.150_602_627_698_910_3),
+    (1, 5.0, 3.0, 9.894_705_615_262_72),
+    (3, -2.0, 10.0, 0.001_398_828_678_402_740_2),
+    (0, 30.0, 12.0, 18.000_000_015_229_98),
+  ];
+  for (j, eta, x, f) in cases {
+    assert_close(fermi_dirac_integral_incomplete(j, eta, x), f, 4.0e-15);
+  }
+}
@@ -0,0 +1,549 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
+use crate::utils::constants;
+use crate::utils::scalar::Scalar;
+
+// Physical quantities which carry their dimension in their type, so that adding a length to a time
+// or passing an energy where a temperature is expected fails to compile. The dimension is a list of
+// the exponents of the metre, kilogram, second and kelvin, written as types, so that multiplying or
+// dividing any two quantities works out the dimension of the result at compile time, and a result
+// with no dimension left, such as a ratio of two lengths, is a plain number. The value is always
+// held in SI units and the dimension is zero-sized, so a Quantity costs exactly as much as the bare
+// number inside it. Units only come into play when a quantity is created or read back out.
+//
+// The value may be any Scalar, so that functions of quantities can still be differentiated with
+// dual numbers or complex steps.
+#[repr(transparent)]
+pub struct Quantity<D, T = f64> {
+  value: T,
+  dimension: PhantomData<D>,
+}
+
+pub mod dimension {
+  use std::marker::PhantomData;
+
+  // Integers as types: Z is zero, P<A> is A + 1 and N<A> is A - 1, where P only ever wraps Z or
+  // another P, and N likewise, so that each integer has just the one type.
+  pub struct Z;
+  pub struct P<A>(PhantomData<A>);
+  pub struct N<A>(PhantomData<A>);
+
+  pub type P1 = P<Z>;
+  pub type P2 = P<P1>;
+  pub type P3 = P<P2>;
+  pub type N1 = N<Z>;
+  pub type N2 = N<N1>;
+  pub type N3 = N<N2>;
+
+  pub trait Integer {
+    const VALUE: i32;
+  }
+
+  impl Integer for Z {
+    const VALUE: i32 = 0;
+  }
+
+  impl<A: Integer> Integer for P<A> {
+    const VALUE: i32 = A::VALUE + 1;
+  }
+
+  impl<A: Integer> Integer for N<A> {
+    const VALUE: i32 = A::VALUE - 1;
+  }
+
+  // The sum and negation of integers, found by peeling off one P or N at a time.
+  pub trait Plus<B> {
+    type Output;
+  }
+
+  impl<B> Plus<B> for Z {
+    type Output = B;
+  }
+
+  impl<A> Plus<Z> for P<A> {
+    type Output = P<A>;
+  }
+
+  impl<A: Plus<B>, B> Plus<P<B>> for P<A> {
+    type Output = P<P<A::Output>>;
+  }
+
+  impl<A: Plus<B>, B> Plus<N<B>> for P<A> {
+    type Output = A::Output;
+  }
+
+  impl<A> Plus<Z> for N<A> {
+    type Output = N<A>;
+  }
+
+  impl<A: Plus<B>, B> Plus<N<B>> for N<A> {
+    type Output = N<N<A::Output>>;
+  }
+
+  impl<A: Plus<B>, B> Plus<P<B>> for N<A> {
+    type Output = A::Output;
+  }
+
+  pub trait Negate {
+    type Output;
+  }
+
+  impl Negate for Z {
+    type Output = Z;
+  }
+
+  impl<A: Negate> Negate for P<A> {
+    type Output = N<A::Output>;
+  }
+
+  impl<A: Negate> Negate for N<A> {
+    type Output = P<A::Output>;
+  }
+
+  // The dimension m^L kg^M s^T K^K.
+  pub struct Dim<L, M, T, K>(PhantomData<(L, M, T, K)>);
+
+  // The dimensions of a product and of a quotient.
+  pub trait Times<D> {
+    type Output;
+  }
+
+  pub trait Per<D> {
+    type Output;
+  }
+
+  impl<L1, M1, T1, K1, L2, M2, T2, K2> Times<Dim<L2, M2, T2, K2>> for Dim<L1, M1, T1, K1>
+  where
+    L1: Plus<L2>,
+    M1: Plus<M2>,
+    T1: Plus<T2>,
+    K1: Plus<K2>,
+  {
+    type Output = Dim<L1::Output, M1::Output, T1::Output, K1::Output>;
+  }
+
+  impl<L1, M1, T1, K1, L2, M2, T2, K2> Per<Dim<L2, M2, T2, K2>> for Dim<L1, M1, T1, K1>
+  where
+    L2: Negate,
+    M2: Negate,
+    T2: Negate,
+    K2: Negate,
+    L1: Plus<L2::Output>,
+    M1: Plus<M2::Output>,
+    T1: Plus<T2::Output>,
+    K1: Plus<K2::Output>,
+  {
+    type Output = Dim<L1::Output, M1::Output, T1::Output, K1::Output>;
+  }
+
+  pub type Dimensionless = Dim<Z, Z, Z, Z>;
+  pub type Length = Dim<P1, Z, Z, Z>;
+  pub type Mass = Dim<Z, P1, Z, Z>;
+  pub type Time = Dim<Z, Z, P1, Z>;
+  pub type Temperature = Dim<Z, Z, Z, P1>;
+  pub type Area = Dim<P2, Z, Z, Z>;
+  pub type Volume = Dim<P3, Z, Z, Z>;
+  pub type NumberDensity = Dim<N3, Z, Z, Z>;
+  pub type Frequency = Dim<Z, Z, N1, Z>;
+  pub type Velocity = Dim<P1, Z, N1, Z>;
+  pub type Acceleration = Dim<P1, Z, N2, Z>;
+  pub type Momentum = Dim<P1, P1, N1, Z>;
+  pub type Force = Dim<P1, P1, N2, Z>;
+  pub type Energy = Dim<P2, P1, N2, Z>;
+  pub type Power = Dim<P2, P1, N3, Z>;
+  pub type Pressure = Dim<N1, P1, N2, Z>;
+  pub type HeatCapacity = Dim<P2, P1, N2, N1>;
+}
+
+use dimension::{Dim, Dimensionless, Integer, Per, Times, N, P, Z};
+
+pub trait Dimension {
+  // The exponents of m, kg, s and K.
+  const EXPONENTS: [i32; 4];
+
+  // What a quantity of this dimension is: a Quantity, or the bare number if it is dimensionless.
+  type Quantity<T>;
+
+  fn from_si<T>(value: T) -> Self::Quantity<T>;
+
+  // The symbol of the coherent SI unit, in which Display prints the value.
+  fn symbol() -> String {
+    let named = match Self::EXPONENTS {
+      [1, 1, -2, 0] => "N",
+      [2, 1, -2, 0] => "J",
+      [2, 1, -3, 0] => "W",
+      [-1, 1, -2, 0] => "Pa",
+      [2, 1, -2, -1] => "J/K",
+      [0, 0, -1, 0] => "Hz",
+      [1, 0, -1, 0] => "m/s",
+      [1, 0, -2, 0] => "m/s^2",
+      [1, 1, -1, 0] => "kg m/s",
+      _ => "",
+    };
+    if !named.is_empty() {
+      return named.to_string();
+    }
+    let [length, mass, time, temperature] = Self::EXPONENTS;
+    [("kg", mass), ("m", length), ("s", time), ("K", temperature)].iter()
+      .filter(|(_, exponent)| *exponent != 0)
+      .map(|(symbol, exponent)| if *exponent == 1 { symbol.to_string() } else { format!("{symbol}^{exponent}") })
+      .collect::<Vec<_>>()
+      .join(" ")
+  }
+}
+
+impl Dimension for Dim<Z, Z, Z, Z> {
+  const EXPONENTS: [i32; 4] = [0, 0, 0, 0];
+  type Quantity<T> = T;
+
+  fn from_si<T>(value: T) -> T {
+    value
+  }
+}
+
+// Every other dimension, told apart from the dimensionless one by its first nonzero exponent.
+macro_rules! dimensional {
+  ($(<$($param:ident),*> $l:ty, $m:ty, $t:ty, $k:ty;)*) => {
+    $(
+      impl<$($param: Integer),*> Dimension for Dim<$l, $m, $t, $k> {
+        const EXPONENTS: [i32; 4] = [<$l>::VALUE, <$m>::VALUE, <$t>::VALUE, <$k>::VALUE];
+        type Quantity<V> = Quantity<Self, V>;
+
+        fn from_si<V>(value: V) -> Quantity<Self, V> {
+          Quantity { value, dimension: PhantomData }
+        }
+      }
+    )*
+  };
+}
+
+dimensional! {
+  <A, M, T, K> P<A>, M, T, K;
+  <A, M, T, K> N<A>, M, T, K;
+  <A, T, K> Z, P<A>, T, K;
+  <A, T, K> Z, N<A>, T, K;
+  <A, K> Z, Z, P<A>, K;
+  <A, K> Z, Z, N<A>, K;
+  <A> Z, Z, Z, P<A>;
+  <A> Z, Z, Z, N<A>;
+}
+
+pub type Length<T = f64> = Quantity<dimension::Length, T>;
+pub type Mass<T = f64> = Quantity<dimension::Mass, T>;
+pub type Time<T = f64> = Quantity<dimension::Time, T>;
+pub type Temperature<T = f64> = Quantity<dimension::Temperature, T>;
+pub type Area<T = f64> = Quantity<dimension::Area, T>;
+pub type Volume<T = f64> = Quantity<dimension::Volume, T>;
+pub type NumberDensity<T = f64> = Quantity<dimension::NumberDensity, T>;
+pub type Frequency<T = f64> = Quantity<dimension::Frequency, T>;
+pub type Velocity<T = f64> = Quantity<dimension::Velocity, T>;
+pub type Acceleration<T = f64> = Quantity<dimension::Acceleration, T>;
+pub type Momentum<T = f64> = Quantity<dimension::Momentum, T>;
+pub type Force<T = f64> = Quantity<dimension::Force, T>;
+pub type Energy<T = f64> = Quantity<dimension::Energy, T>;
+pub type Power<T = f64> = Quantity<dimension::Power, T>;
+pub type Pressure<T = f64> = Quantity<dimension::Pressure, T>;
+pub type HeatCapacity<T = f64> = Quantity<dimension::HeatCapacity, T>;
+
+// A unit of dimension D: a value v in this unit is v * factor + offset in SI units. Only the
+// temperature scales have an offset, which makes sense for absolute temperatures but not for
+// differences between them, so temperature differences should be given in kelvin.
+#[derive(Debug, PartialEq)]
+pub struct Unit<D> {
+  pub factor: f64,
+  pub offset: f64,
+  pub symbol: &'static str,
+  dimension: PhantomData<D>,
+}
+
+impl<D> Unit<D> {
+  pub const fn new(factor: f64, symbol: &'static str) -> Unit<D> {
+    Unit::with_offset(factor, 0.0, symbol)
+  }
+
+  pub const fn with_offset(factor: f64, offset: f64, symbol: &'static str) -> Unit<D> {
+    Unit { factor, offset, symbol, dimension: PhantomData }
+  }
+}
+
+impl<D> Clone for Unit<D> {
+  fn clone(&self) -> Unit<D> {
+    *self
+  }
+}
+
+impl<D> Copy for Unit<D> {}
+
+pub const METER: Unit<dimension::Length> = Unit::new(1.0, "m");
+pub const KILOMETER: Unit<dimension::Length> = Unit::new(1.0e3, "km");
+pub const CENTIMETER: Unit<dimension::Length> = Unit::new(1.0e-2, "cm");
+pub const MILLIMETER: Unit<dimension::Length> = Unit::new(1.0e-3, "mm");
+pub const NANOMETER: Unit<dimension::Length> = Unit::new(1.0e-9, "nm");
+pub const ANGSTROM: Unit<dimension::Length> = Unit::new(1.0e-10, "Å");
+
+pub const KILOGRAM: Unit<dimension::Mass> = Unit::new(1.0, "kg");
+pub const GRAM: Unit<dimension::Mass> = Unit::new(1.0e-3, "g");
+
+pub const SECOND: Unit<dimension::Time> = Unit::new(1.0, "s");
+pub const MILLISECOND: Unit<dimension::Time> = Unit::new(1.0e-3, "ms");
+pub const MINUTE: Unit<dimension::Time> = Unit::new(60.0, "min");
+pub const HOUR: Unit<dimension::Time> = Unit::new(3600.0, "h");
+
+pub const KELVIN: Unit<dimension::Temperature> = Unit::new(1.0, "K");
+pub const CELSIUS: Unit<dimension::Temperature> = Unit::with_offset(1.0, 273.15, "°C");
+
+pub const SQUARE_METER: Unit<dimension::Area> = Unit::new(1.0, "m^2");
+pub const SQUARE_CENTIMETER: Unit<dimension::Area> = Unit::new(1.0e-4, "cm^2");
+
+pub const CUBIC_METER: Unit<dimension::Volume> = Unit::new(1.0, "m^3");
+pub const CUBIC_CENTIMETER: Unit<dimension::Volume> = Unit::new(1.0e-6, "cm^3");
+pub const LITER: Unit<dimension::Volume> = Unit::new(1.0e-3, "L");
+
+pub const PER_CUBIC_METER: Unit<dimension::NumberDensity> = Unit::new(1.0, "m^-3");
+pub const PER_CUBIC_CENTIMETER: Unit<dimension::NumberDensity> = Unit::new(1.0e6, "cm^-3");
+
+pub const HERTZ: Unit<dimension::Frequency> = Unit::new(1.0, "Hz");
+
+pub const METER_PER_SECOND: Unit<dimension::Velocity> = Unit::new(1.0, "m/s");
+pub const KILOMETER_PER_HOUR: Unit<dimension::Velocity> = Unit::new(1.0e3 / 3600.0, "km/h");
+
+pub const METER_PER_SECOND_SQUARED: Unit<dimension::Acceleration> = Unit::new(1.0, "m/s^2");
+
+pub const KILOGRAM_METER_PER_SECOND: Unit<dimension::Momentum> = Unit::new(1.0, "kg m/s");
+
+pub const NEWTON: Unit<dimension::Force> = Unit::new(1.0, "N");
+
+pub const JOULE: Unit<dimension::Energy> = Unit::new(1.0, "J");
+pub const KILOJOULE: Unit<dimension::Energy> = Unit::new(1.0e3, "kJ");
+pub const CALORIE: Unit<dimension::Energy> = Unit::new(4.184, "cal");
+pub const ELECTRON_VOLT: Unit<dimension::Energy> = Unit::new(constants::ELECTRON_VOLT.value, "eV");
+
+pub const WATT: Unit<dimension::Power> = Unit::new(1.0, "W");
+
+pub const PASCAL: Unit<dimension::Pressure> = Unit::new(1.0, "Pa");
+pub const BAR: Unit<dimension::Pressure> = Unit::new(1.0e5, "bar");
+pub const ATMOSPHERE: Unit<dimension::Pressure> = Unit::new(101_325.0, "atm");
+
+pub const JOULE_PER_KELVIN: Unit<dimension::HeatCapacity> = Unit::new(1.0, "J/K");
+
+impl<D, T: Scalar> Quantity<D, T> {
+  pub fn new(value: T, unit: Unit<D>) -> Quantity<D, T> {
+    Quantity::from_si(value * unit.factor + unit.offset)
+  }
+
+  pub fn from_si(value: T) -> Quantity<D, T> {
+    Quantity { value, dimension: PhantomData }
+  }
+
+  pub fn si_value(self) -> T {
+    self.value
+  }
+
+  pub fn value_in(self, unit: Unit<D>) -> T {
+    (self.value - unit.offset) / unit.factor
+  }
+
+  // Formats the quantity in the given unit instead of the SI one.
+  pub fn display_in(self, unit: Unit<D>) -> DisplayIn<D, T> {
+    DisplayIn { quantity: self, unit }
+  }
+}
+
+impl<D> Quantity<D, f64> {
+  // The same quantity with its value carried by another scalar type, as when a fixed parameter
+  // enters a calculation done in dual or complex numbers.
+  pub fn cast<T: Scalar>(self) -> Quantity<D, T> {
+    Quantity::from_si(T::from(self.value))
+  }
+
+  pub fn abs(self) -> Quantity<D, f64> {
+    Quantity::from_si(self.value.abs())
+  }
+}
+
+impl<D, T: Copy> Clone for Quantity<D, T> {
+  fn clone(&self) -> Quantity<D, T> {
+    *self
+  }
+}
+
+impl<D, T: Copy> Copy for Quantity<D, T> {}
+
+impl<D, T: PartialEq> PartialEq for Quantity<D, T> {
+  fn eq(&self, other: &Quantity<D, T>) -> bool {
+    self.value == other.value
+  }
+}
+
+impl<D, T: PartialOrd> PartialOrd for Quantity<D, T> {
+  fn partial_cmp(&self, other: &Quantity<D, T>) -> Option<Ordering> {
+    self.value.partial_cmp(&other.value)
+  }
+}
+
+impl<D: Dimension, T: fmt::Debug> fmt::Debug for Quantity<D, T> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{:?} {}", self.value, D::symbol())
+  }
+}
+
+// Any format options, such as a precision or {:e}, apply to the number.
+impl<D: Dimension, T: fmt::Display> fmt::Display for Quantity<D, T> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    self.value.fmt(f)?;
+    write!(f, " {}", D::symbol())
+  }
+}
+
+pub struct DisplayIn<D, T> {
+  quantity: Quantity<D, T>,
+  unit: Unit<D>,
+}
+
+impl<D, T: Scalar + fmt::Display> fmt::Display for DisplayIn<D, T> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    self.quantity.value_in(self.unit).fmt(f)?;
+    write!(f, " {}", self.unit.symbol)
+  }
+}
+
+impl<D, T: Scalar> Neg for Quantity<D, T> {
+  type Output = Quantity<D, T>;
+  fn neg(self) -> Quantity<D, T> {
+    Quantity::from_si(-self.value)
+  }
+}
+
+impl<D, T: Scalar> Add for Quantity<D, T> {
+  type Output = Quantity<D, T>;
+  fn add(self, rhs: Quantity<D, T>) -> Quantity<D, T> {
+    Quantity::from_si(self.value + rhs.value)
+  }
+}
+
+impl<D, T: Scalar> Sub for Quantity<D, T> {
+  type Output = Quantity<D, T>;
+  fn sub(self, rhs: Quantity<D, T>) -> Quantity<D, T> {
+    Quantity::from_si(self.value - rhs.value)
+  }
+}
+
+impl<D, T: Scalar> AddAssign for Quantity<D, T> {
+  fn add_assign(&mut self, rhs: Quantity<D, T>) {
+    *self = *self + rhs;
+  }
+}
+
+impl<D, T: Scalar> SubAssign for Quantity<D, T> {
+  fn sub_assign(&mut self, rhs: Quantity<D, T>) {
+    *self = *self - rhs;
+  }
+}
+
+impl<D, T: Scalar> Mul<T> for Quantity<D, T> {
+  type Output = Quantity<D, T>;
+  fn mul(self, rhs: T) -> Quantity<D, T> {
+    Quantity::from_si(self.value * rhs)
+  }
+}
+
+impl<D, T: Scalar> Div<T> for Quantity<D, T> {
+  type Output = Quantity<D, T>;
+  fn div(self, rhs: T) -> Quantity<D, T> {
+    Quantity::from_si(self.value / rhs)
+  }
+}
+
+impl<D, T: Scalar> Mul<Quantity<D, T>> for f64 {
+  type Output = Quantity<D, T>;
+  fn mul(self, rhs: Quantity<D, T>) -> Quantity<D, T> {
+    Quantity::from_si(rhs.value * self)
+  }
+}
+
+impl<D, T: Scalar> Div<Quantity<D, T>> for f64
+where
+  Dimensionless: Per<D, Output: Dimension>,
+{
+  type Output = <<Dimensionless as Per<D>>::Output as Dimension>::Quantity<T>;
+  fn div(self, rhs: Quantity<D, T>) -> Self::Output {
+    <Dimensionless as Per<D>>::Output::from_si(T::from(self) / rhs.value)
+  }
+}
+
+// Any two quantities multiply and divide, to a quantity of the combined dimension, or to a plain
+// number if the dimensions cancel.
+impl<D1: Times<D2, Output: Dimension>, D2, T: Scalar> Mul<Quantity<D2, T>> for Quantity<D1, T> {
+  type Output = <D1::Output as Dimension>::Quantity<T>;
+  fn mul(self, rhs: Quantity<D2, T>) -> Self::Output {
+    D1::Output::from_si(self.value * rhs.value)
+  }
+}
+
+impl<D1: Per<D2, Output: Dimension>, D2, T: Scalar> Div<Quantity<D2, T>> for Quantity<D1, T> {
+  type Output = <D1::Output as Dimension>::Quantity<T>;
+  fn div(self, rhs: Quantity<D2, T>) -> Self::Output {
+    D1::Output::from_si(self.value / rhs.value)
+  }
+}
+
+#[test]
+fn test_units() {
+  use crate::utils::dual::Dual;
+
+  assert_eq!(std::mem::size_of::<Temperature>(), std::mem::size_of::<f64>());
+
+  // Conversions round trip, including the offset of the Celsius scale.
+  let volume = Volume::new(1000.0, CUBIC_CENTIMETER);
+  println!("{volume} = {}", volume.display_in(LITER));
+  assert!((volume.si_value() - 1.0e-3).abs() < 1.0e-18);
+  assert!((volume.value_in(LITER) - 1.0).abs() < 1.0e-15);
+  assert_eq!(format!("{:.1}", volume.display_in(LITER)), "1.0 L");
+
+  let boiling = Temperature::new(100.0, CELSIUS);
+  assert_eq!(boiling, Temperature::new(373.15, KELVIN));
+  assert_eq!(boiling.value_in(CELSIUS), 100.0);
+  assert_eq!(format!("{boiling:.2}"), "373.15 K");
+
+  // Products and quotients carry their dimension through.
+  let side = Length::new(2.0, CENTIMETER);
+  let cube: Volume = side * side * side;
+  assert!((cube.value_in(CUBIC_CENTIMETER) - 8.0).abs() < 1.0e-14);
+  assert!((cube / (side * side) / side - 1.0).abs() < 1.0e-15);
+
+  let speed = Velocity::new(36.0, KILOMETER_PER_HOUR);
+  let distance = speed * Time::new(1.0, MINUTE);
+  assert!((distance.value_in(METER) - 600.0).abs() < 1.0e-12);
+
+  let work = Pressure::new(1.0, ATMOSPHERE) * Volume::new(1.0, LITER);
+  assert!((work.value_in(JOULE) - 101.325).abs() < 1.0e-12);
+
+  let atoms = NumberDensity::new(6.002e22, PER_CUBIC_CENTIMETER) * volume;
+  assert!((atoms / 6.002e25 - 1.0).abs() < 1.0e-15);
+
+  let heat = HeatCapacity::new(2.0, JOULE_PER_KELVIN) * Temperature::new(3.0, KELVIN);
+  assert_eq!(heat, Energy::new(6.0, JOULE));
+  assert_eq!(heat / Temperature::new(3.0, KELVIN), HeatCapacity::new(2.0, JOULE_PER_KELVIN));
+  assert!(Energy::new(1.0, ELECTRON_VOLT) < Energy::new(1.0, JOULE));
+
+  // Any product works out its own dimension, and prints in the coherent SI unit.
+  let momentum: Momentum = Mass::new(2.0, KILOGRAM) * Velocity::new(3.0, METER_PER_SECOND);
+  assert_eq!(momentum / Time::new(2.0, SECOND), Force::new(3.0, NEWTON));
+  assert_eq!(format!("{momentum}"), "6 kg m/s");
+  assert_eq!(format!("{}", speed * speed), "100 m^2 s^-2");
+  assert_eq!(format!("{}", HeatCapacity::new(1.0, JOULE_PER_KELVIN) / Mass::new(1.0, KILOGRAM)), "1 m^2 s^-2 K^-1");
+  let frequency: Frequency = 1.0 / Time::new(1.0, MILLISECOND);
+  assert!((frequency.value_in(HERTZ) - 1000.0).abs() < 1.0e-12);
+  assert!((frequency * Time::new(1.0, SECOND) - 1000.0).abs() < 1.0e-12);
+
+  // Quantities can hold dual numbers, so functions of them can be differentiated: the kinetic
+  // energy m v^2 / 2 has derivative m v with respect to v.
+  let mass = Mass::new(3.0, KILOGRAM).cast::<Dual>();
+  let v = Velocity::from_si(Dual::variable(2.0));
+  let kinetic_energy: Energy<Dual> = 0.5 * mass * v * v;
+  println!("{kinetic_energy}");
+  assert_eq!(kinetic_energy.si_value(), Dual::new(6.0, 6.0));
+}